@@ -0,0 +1,77 @@
+//! Backlink graph for wiki-style cross-references.
+//!
+//! Every indexed entry can be the target of a `[[id]]` link. Each entry's
+//! body is scanned for such links and, for every entry linked to, the listed
+//! entries linking to it are embedded into its rendered JSON as "Referenced by".
+
+use crate::markdown::options;
+use crate::related::related_entry;
+use crate::rendered::RelatedEntry;
+use crate::wikilink::LinkTargets;
+use crate::{Date, Indexed, COLLECTIONS};
+use pulldown_cmark::{Event, LinkType, Parser, Tag};
+use std::collections::{HashMap, HashSet};
+
+/// Every indexed entry, for resolving wiki links.
+pub fn link_targets(indexed: &[Indexed]) -> LinkTargets {
+    let mut targets = LinkTargets::new(COLLECTIONS);
+    for collection in indexed {
+        for entry in &collection.entries {
+            targets.push(collection.collection, &entry.meta.id, &entry.meta.title);
+        }
+    }
+    targets
+}
+
+/// Entries linking to each entry, newest first, keyed by `{type}/{id}`.
+pub fn compute_backlinks(
+    indexed: &[Indexed],
+    targets: &LinkTargets,
+) -> HashMap<String, Vec<RelatedEntry>> {
+    let mut backlinks: HashMap<String, Vec<(Date, RelatedEntry)>> = HashMap::new();
+    for indexed in indexed {
+        for entry in &indexed.entries {
+            // A "Referenced by" link would reveal an unlisted entry
+            if !entry.meta.status.is_listed() {
+                continue;
+            }
+            let source = format!("{}/{}", indexed.collection.name, entry.meta.id);
+            let mut linked = HashSet::new();
+            for reference in wiki_links(&entry.markdown) {
+                let Ok(target) = targets.resolve(&reference) else {
+                    continue;
+                };
+                let key = format!("{}/{}", target.collection.name, target.id);
+                // Self-links are no reference, and repeated links count once
+                if key == source || !linked.insert(key.clone()) {
+                    continue;
+                }
+                backlinks.entry(key).or_default().push((
+                    entry.meta.date,
+                    related_entry(indexed.collection, &entry.meta),
+                ));
+            }
+        }
+    }
+    backlinks
+        .into_iter()
+        .map(|(key, mut sources)| {
+            sources.sort_by_key(|(date, _)| std::cmp::Reverse(*date));
+            (key, sources.into_iter().map(|(_, entry)| entry).collect())
+        })
+        .collect()
+}
+
+/// The targets of an entry body's `[[...]]` links.
+fn wiki_links(markdown: &str) -> Vec<String> {
+    Parser::new_ext(markdown, options())
+        .filter_map(|event| match event {
+            Event::Start(Tag::Link {
+                link_type: LinkType::WikiLink { .. },
+                dest_url,
+                ..
+            }) => Some(dest_url.to_string()),
+            _ => None,
+        })
+        .collect()
+}
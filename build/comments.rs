@@ -0,0 +1,84 @@
+//! Static comments.
+//!
+//! Checks the `comments.json` file of every indexed entry that has one, renders
+//! its comments to sanitized HTML in `public/content/comments/{type}/{id}.json`,
+//! so the app needs no markdown parser for them, and writes
+//! `public/content/comment_counts.json`, the per-entry comment counts the static
+//! comment backend shows on cards.
+
+use crate::comment::{check, rendered_comments_file, CommentRecord, COMMENTS_FILE, COUNTS_PATH};
+use crate::constants::COMMENT_POLICY;
+use crate::markdown::render_comment;
+use crate::validate::Report;
+use crate::{write_if_changed, Indexed};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const PUBLIC_DIR: &str = "public";
+const OUTPUT_DIR: &str = "content/comments";
+
+/// Writes the rendered comments and comment counts of every entry with static comments.
+pub fn write_comments(indexed: &[Indexed], report: &mut Report) {
+    let mut counts: BTreeMap<&str, BTreeMap<&str, usize>> = BTreeMap::new();
+    let mut written = HashSet::new();
+    for indexed in indexed {
+        let name = indexed.collection.name;
+        for entry in &indexed.entries {
+            let path = Path::new("public/content")
+                .join(name)
+                .join(&entry.meta.id)
+                .join(COMMENTS_FILE);
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let mut comments: Vec<CommentRecord> = match serde_json::from_str(&content) {
+                Ok(comments) => comments,
+                Err(e) => {
+                    report.warn(&path, Some(e.line()), format!("invalid comments: {}", e));
+                    continue;
+                }
+            };
+            for problem in check(&comments) {
+                report.warn(&path, None, problem);
+            }
+            if comments.is_empty() {
+                continue;
+            }
+            counts
+                .entry(name)
+                .or_default()
+                .insert(&entry.meta.id, comments.len());
+
+            for comment in &mut comments {
+                comment.html = Some(render_comment(&comment.body, &COMMENT_POLICY));
+            }
+            let output = Path::new(PUBLIC_DIR).join(rendered_comments_file(name, &entry.meta.id));
+            write_if_changed(&output, &serde_json::to_string(&comments).unwrap());
+            written.insert(output);
+        }
+    }
+
+    write_if_changed(
+        Path::new(PUBLIC_DIR).join(COUNTS_PATH),
+        &serde_json::to_string_pretty(&counts).unwrap(),
+    );
+    prune(&Path::new(PUBLIC_DIR).join(OUTPUT_DIR), &written);
+}
+
+/// Deletes rendered comments of entries whose `comments.json` is gone or no longer indexed.
+fn prune(dir: &Path, keep: &HashSet<PathBuf>) {
+    for collection in fs::read_dir(dir).into_iter().flatten().flatten() {
+        for file in fs::read_dir(collection.path())
+            .into_iter()
+            .flatten()
+            .flatten()
+        {
+            if !keep.contains(&file.path()) {
+                let _ = fs::remove_file(file.path());
+            }
+        }
+        // Only succeeds once the folder is empty
+        let _ = fs::remove_dir(collection.path());
+    }
+}
@@ -0,0 +1,301 @@
+//! RSS 2.0, Atom and JSON Feed generation for collections with `feed` enabled.
+//!
+//! Feeds are written to `public/` (site-wide) and `public/tags/{tag}/`
+//! (per tag), with every URL made absolute via [`SiteConfig`].
+
+use crate::constants::{
+    APP_SUBTITLE, APP_TITLE, AUTHOR_NAME, CONTENT_POLICY, FEED_FULL_CONTENT,
+    TIMEZONE_OFFSET_MINUTES,
+};
+use crate::date::Date;
+use crate::html::escape;
+use crate::markdown::render_markdown;
+use crate::site::SiteConfig;
+use crate::slug::slugify;
+use crate::wikilink::LinkTargets;
+use crate::{write_if_changed, Entry, EntryMeta, Indexed, PublishStatus};
+use serde_json::json;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+const OUTPUT_DIR: &str = "public";
+
+/// An entry prepared for every feed format.
+struct FeedItem<'a> {
+    meta: &'a EntryMeta,
+    url: String,
+    image: String,
+    content_html: String,
+}
+
+/// Writes site-wide and per-tag feeds for all published entries of feed collections.
+pub fn write_feeds(site: &SiteConfig, indexed: &[Indexed], targets: &LinkTargets) {
+    let mut published: Vec<(&Indexed, &Entry)> = indexed
+        .iter()
+        .filter(|indexed| indexed.collection.feed)
+        .flat_map(|indexed| indexed.entries.iter().map(move |entry| (indexed, entry)))
+        .filter(|(_, entry)| entry.meta.status == PublishStatus::Published)
+        .collect();
+    published.sort_by_key(|(_, entry)| std::cmp::Reverse(entry.meta.date));
+
+    let items: Vec<FeedItem> = published
+        .iter()
+        .map(|(indexed, entry)| {
+            let collection = indexed.collection;
+            FeedItem {
+                meta: &entry.meta,
+                url: site.url(&format!("/{}/{}", collection.route, entry.meta.id)),
+                image: site.asset_url(&entry.meta.image_url),
+                content_html: if FEED_FULL_CONTENT {
+                    render_markdown(
+                        &entry.markdown,
+                        &entry.meta.id,
+                        collection,
+                        &site.root_url(),
+                        targets,
+                        &CONTENT_POLICY,
+                    )
+                } else {
+                    format!("<p>{}</p>", escape(&entry.meta.description))
+                },
+            }
+        })
+        .collect();
+
+    let all: Vec<&FeedItem> = items.iter().collect();
+    write_feed_set(site, Path::new(OUTPUT_DIR), "", APP_TITLE, &all);
+
+    // Tags are canonical by now, so every spelling of a slug is its display name
+    let mut by_tag: BTreeMap<String, (&str, Vec<&FeedItem>)> = BTreeMap::new();
+    for item in &items {
+        for tag in &item.meta.tags {
+            by_tag
+                .entry(slugify(tag))
+                .or_insert_with(|| (tag, Vec::new()))
+                .1
+                .push(item);
+        }
+    }
+
+    let tags_dir = Path::new(OUTPUT_DIR).join("tags");
+    for (slug, (name, tagged)) in &by_tag {
+        let title = format!("{} - #{}", APP_TITLE, name);
+        write_feed_set(
+            site,
+            &tags_dir.join(slug),
+            &format!("/tags/{}", slug),
+            &title,
+            tagged,
+        );
+    }
+    prune_stale_tags(&tags_dir, &by_tag.keys().cloned().collect());
+}
+
+/// Writes `feed.xml`, `atom.xml` and `feed.json` into `dir`, served at `url_prefix`.
+fn write_feed_set(
+    site: &SiteConfig,
+    dir: &Path,
+    url_prefix: &str,
+    title: &str,
+    items: &[&FeedItem],
+) {
+    write_if_changed(dir.join("feed.xml"), &rss(site, url_prefix, title, items));
+    write_if_changed(dir.join("atom.xml"), &atom(site, url_prefix, title, items));
+    write_if_changed(
+        dir.join("feed.json"),
+        &json_feed(site, url_prefix, title, items),
+    );
+}
+
+fn rss(site: &SiteConfig, url_prefix: &str, title: &str, items: &[&FeedItem]) -> String {
+    let last_build = items
+        .first()
+        .map(|item| {
+            format!(
+                "  <lastBuildDate>{}</lastBuildDate>\n",
+                rfc822(item.meta.date)
+            )
+        })
+        .unwrap_or_default();
+    let entries: String = items
+        .iter()
+        .map(|item| {
+            let categories: String = item
+                .meta
+                .tags
+                .iter()
+                .map(|tag| format!("    <category>{}</category>\n", escape(tag)))
+                .collect();
+            format!(
+                r#"  <item>
+    <title>{title}</title>
+    <link>{url}</link>
+    <guid isPermaLink="true">{url}</guid>
+    <pubDate>{date}</pubDate>
+    <dc:creator>{author}</dc:creator>
+{categories}    <description>{content}</description>
+  </item>
+"#,
+                title = escape(&item.meta.title),
+                url = escape(&item.url),
+                date = rfc822(item.meta.date),
+                author = escape(&item.meta.author),
+                content = cdata(&item.content_html),
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom" xmlns:dc="http://purl.org/dc/elements/1.1/">
+<channel>
+  <title>{title}</title>
+  <link>{root}/</link>
+  <description>{description}</description>
+  <atom:link href="{self_url}" rel="self" type="application/rss+xml"/>
+{last_build}{entries}</channel>
+</rss>
+"#,
+        title = escape(title),
+        root = escape(&site.root_url()),
+        description = escape(APP_SUBTITLE),
+        self_url = escape(&site.url(&format!("{}/feed.xml", url_prefix))),
+    )
+}
+
+fn atom(site: &SiteConfig, url_prefix: &str, title: &str, items: &[&FeedItem]) -> String {
+    let updated = items
+        .first()
+        .map_or(Date::from_unix(0, 0), |item| item.meta.date);
+    let entries: String = items
+        .iter()
+        .map(|item| {
+            let categories: String = item
+                .meta
+                .tags
+                .iter()
+                .map(|tag| format!("    <category term=\"{}\"/>\n", escape(tag)))
+                .collect();
+            format!(
+                r#"  <entry>
+    <title>{title}</title>
+    <link href="{url}"/>
+    <id>{url}</id>
+    <published>{date}</published>
+    <updated>{date}</updated>
+    <author><name>{author}</name></author>
+{categories}    <summary>{summary}</summary>
+    <content type="html">{content}</content>
+  </entry>
+"#,
+                title = escape(&item.meta.title),
+                url = escape(&item.url),
+                date = rfc3339(item.meta.date),
+                author = escape(&item.meta.author),
+                summary = escape(&item.meta.description),
+                content = escape(&item.content_html),
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>{title}</title>
+  <subtitle>{subtitle}</subtitle>
+  <link href="{root}/"/>
+  <link rel="self" href="{self_url}"/>
+  <id>{feed_id}</id>
+  <updated>{updated}</updated>
+  <author><name>{author}</name></author>
+{entries}</feed>
+"#,
+        title = escape(title),
+        subtitle = escape(APP_SUBTITLE),
+        root = escape(&site.root_url()),
+        self_url = escape(&site.url(&format!("{}/atom.xml", url_prefix))),
+        feed_id = escape(&site.url(&format!("{}/", url_prefix))),
+        updated = rfc3339(updated),
+        author = escape(AUTHOR_NAME),
+    )
+}
+
+fn json_feed(site: &SiteConfig, url_prefix: &str, title: &str, items: &[&FeedItem]) -> String {
+    let entries: Vec<_> = items
+        .iter()
+        .map(|item| {
+            json!({
+                "id": item.url,
+                "url": item.url,
+                "title": item.meta.title,
+                "summary": item.meta.description,
+                "content_html": item.content_html,
+                "image": item.image,
+                "date_published": rfc3339(item.meta.date),
+                "authors": [{ "name": item.meta.author }],
+                "tags": item.meta.tags,
+            })
+        })
+        .collect();
+    let feed = json!({
+        "version": "https://jsonfeed.org/version/1.1",
+        "title": title,
+        "description": APP_SUBTITLE,
+        "home_page_url": format!("{}/", site.root_url()),
+        "feed_url": site.url(&format!("{}/feed.json", url_prefix)),
+        "authors": [{ "name": AUTHOR_NAME }],
+        "items": entries,
+    });
+    serde_json::to_string_pretty(&feed).unwrap()
+}
+
+/// Removes `public/tags/{tag}` feed folders for tags no published entry uses anymore.
+fn prune_stale_tags(tags_dir: &Path, current: &BTreeSet<String>) {
+    let Ok(entries) = fs::read_dir(tags_dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if entry.path().is_dir() && !current.contains(&name) {
+            let _ = fs::remove_dir_all(entry.path());
+        }
+    }
+}
+
+fn cdata(html: &str) -> String {
+    format!("<![CDATA[{}]]>", html.replace("]]>", "]]]]><![CDATA[>"))
+}
+
+/// Midnight of a date in the site timezone, as an RFC 3339 timestamp.
+fn rfc3339(date: Date) -> String {
+    format!("{}T00:00:00{}", date, utc_offset(":"))
+}
+
+/// Midnight of a date in the site timezone, as an RFC 822 date as required by RSS 2.0.
+fn rfc822(date: Date) -> String {
+    const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+    format!(
+        "{}, {} 00:00:00 {}",
+        WEEKDAYS[date.weekday()],
+        date.format("%d %b %Y", "en"),
+        utc_offset("")
+    )
+}
+
+/// [`TIMEZONE_OFFSET_MINUTES`] as `+HH:MM` (or `+HHMM` with an empty separator).
+fn utc_offset(separator: &str) -> String {
+    let sign = if TIMEZONE_OFFSET_MINUTES < 0 {
+        '-'
+    } else {
+        '+'
+    };
+    let minutes = TIMEZONE_OFFSET_MINUTES.unsigned_abs();
+    format!(
+        "{}{:02}{}{:02}",
+        sign,
+        minutes / 60,
+        separator,
+        minutes % 60
+    )
+}
@@ -0,0 +1,234 @@
+//! Responsive variants for cover images and images referenced in markdown.
+//!
+//! Every local raster image gets AVIF and WebP copies at a few widths under
+//! `public/content/images/`, plus a tiny blurred placeholder that is inlined into
+//! the index. Variants are only re-encoded when the source changes.
+
+use crate::markdown::options;
+use crate::path::{entry_file, is_entry_relative};
+use crate::responsive::{ResponsiveImage, IMAGE_FORMATS};
+use crate::validate::Report;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use image::codecs::avif::AvifEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageReader, ImageResult};
+use pulldown_cmark::{Event, Parser, Tag};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+const PUBLIC_DIR: &str = "public";
+const OUTPUT_DIR: &str = "content/images";
+
+/// Widths generated for each image; larger sources are also capped at the last one.
+const WIDTHS: &[u32] = &[480, 960, 1440];
+const PLACEHOLDER_WIDTH: u32 = 16;
+const PLACEHOLDER_BLUR: f32 = 1.0;
+const AVIF_QUALITY: u8 = 70;
+const WEBP_QUALITY: f32 = 75.0;
+/// rav1e speed preset (1-10); higher is faster at a small size cost.
+const AVIF_SPEED: u8 = 8;
+/// GIFs are served as they are, since re-encoding would drop their animation.
+const RASTER_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "webp"];
+
+/// Tracks generated files so variants of removed images can be pruned.
+#[derive(Default)]
+pub struct ImagePipeline {
+    written: HashSet<PathBuf>,
+}
+
+impl ImagePipeline {
+    /// Processes a site-relative image (`content/posts/{id}/cover.png`).
+    /// Remote, root-relative and non-raster images are left alone.
+    pub fn process(
+        &mut self,
+        site_path: &str,
+        md_path: &Path,
+        report: &mut Report,
+    ) -> Option<ResponsiveImage> {
+        let relative = site_path.strip_prefix("content/")?;
+        let source = Path::new(PUBLIC_DIR).join(site_path);
+        let extension = source.extension()?.to_str()?.to_ascii_lowercase();
+        if !RASTER_EXTENSIONS.contains(&extension.as_str()) || !source.is_file() {
+            return None;
+        }
+        let (width, height) = match open(&source).and_then(|reader| reader.into_dimensions()) {
+            Ok(size) => size,
+            Err(e) => {
+                report.warn(
+                    md_path,
+                    None,
+                    format!("cannot read image `{}`: {}", site_path, e),
+                );
+                return None;
+            }
+        };
+
+        // The source extension stays in the name, so `cover.png` and `cover.jpg` never share variants
+        let variants = format!("{}/{}", OUTPUT_DIR, relative);
+        let widths = widths_for(width);
+        let out_base = Path::new(PUBLIC_DIR).join(&variants).display().to_string();
+        let placeholder_path = PathBuf::from(format!("{}-placeholder.webp", out_base));
+        let mut outputs: Vec<(Option<u32>, &str, PathBuf)> = widths
+            .iter()
+            .flat_map(|&width| {
+                let out_base = &out_base;
+                IMAGE_FORMATS.iter().map(move |(ext, _)| {
+                    (
+                        Some(width),
+                        *ext,
+                        PathBuf::from(format!("{}-{}.{}", out_base, width, ext)),
+                    )
+                })
+            })
+            .collect();
+        outputs.push((None, "webp", placeholder_path.clone()));
+
+        if outputs.iter().any(|(_, _, path)| is_stale(&source, path)) {
+            if let Err(e) = encode_variants(&source, &outputs) {
+                report.warn(
+                    md_path,
+                    None,
+                    format!("cannot resize image `{}`: {}", site_path, e),
+                );
+                return None;
+            }
+        }
+        self.written
+            .extend(outputs.into_iter().map(|(_, _, path)| path));
+
+        let placeholder = fs::read(&placeholder_path).ok()?;
+        Some(ResponsiveImage {
+            width,
+            height,
+            variants,
+            widths,
+            placeholder: format!("data:image/webp;base64,{}", STANDARD.encode(placeholder)),
+        })
+    }
+
+    /// Processes every local image referenced by an entry's markdown, keyed by
+    /// its path inside the entry folder, which is what the renderer looks up.
+    pub fn process_markdown(
+        &mut self,
+        markdown: &str,
+        content_type: &str,
+        id: &str,
+        md_path: &Path,
+        report: &mut Report,
+    ) -> HashMap<String, ResponsiveImage> {
+        let mut images = HashMap::new();
+        // Parsed like the renderer, so images in tables and footnotes are found too
+        for event in Parser::new_ext(markdown, options()) {
+            let Event::Start(Tag::Image { dest_url, .. }) = event else {
+                continue;
+            };
+            if !is_entry_relative(&dest_url) {
+                continue;
+            }
+            let path = dest_url.trim_start_matches("./");
+            let site_path = entry_file(content_type, id, path);
+            if let Some(image) = self.process(&site_path, md_path, report) {
+                images.insert(path.to_string(), image);
+            }
+        }
+        images
+    }
+
+    /// Deletes generated files that no indexed entry references anymore.
+    pub fn prune(&self) {
+        prune_dir(&Path::new(PUBLIC_DIR).join(OUTPUT_DIR), &self.written);
+    }
+}
+
+/// The configured widths narrower than the source, plus the source width itself (capped).
+fn widths_for(width: u32) -> Vec<u32> {
+    let max = WIDTHS[WIDTHS.len() - 1];
+    let mut widths: Vec<u32> = WIDTHS.iter().copied().filter(|&w| w < width).collect();
+    let largest = width.min(max);
+    if !widths.contains(&largest) {
+        widths.push(largest);
+    }
+    widths
+}
+
+/// Opens an image by its content rather than its extension, which is not always accurate.
+fn open(path: &Path) -> ImageResult<ImageReader<BufReader<File>>> {
+    Ok(ImageReader::open(path)?.with_guessed_format()?)
+}
+
+fn is_stale(source: &Path, output: &Path) -> bool {
+    let modified = |path: &Path| fs::metadata(path).and_then(|meta| meta.modified()).ok();
+    match (modified(source), modified(output)) {
+        (Some(source), Some(output)) => source > output,
+        _ => true,
+    }
+}
+
+fn encode_variants(source: &Path, outputs: &[(Option<u32>, &str, PathBuf)]) -> ImageResult<()> {
+    let decoded = open(source)?.decode()?;
+    // Both encoders only take 8-bit RGB(A)
+    let image = if decoded.color().has_alpha() {
+        DynamicImage::ImageRgba8(decoded.to_rgba8())
+    } else {
+        DynamicImage::ImageRgb8(decoded.to_rgb8())
+    };
+
+    for (width, extension, path) in outputs {
+        let variant = match width {
+            Some(width) if *width < image.width() => {
+                image.resize(*width, u32::MAX, FilterType::Lanczos3)
+            }
+            Some(_) => image.clone(),
+            None => image
+                .resize(PLACEHOLDER_WIDTH, u32::MAX, FilterType::Triangle)
+                .blur(PLACEHOLDER_BLUR),
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        match *extension {
+            "avif" => {
+                let file = BufWriter::new(File::create(path)?);
+                variant.write_with_encoder(AvifEncoder::new_with_speed_quality(
+                    file,
+                    AVIF_SPEED,
+                    AVIF_QUALITY,
+                ))?
+            }
+            // The `image` crate only writes lossless WebP, which is larger than most sources
+            _ => {
+                let encoder = match &variant {
+                    DynamicImage::ImageRgba8(buffer) => {
+                        webp::Encoder::from_rgba(buffer, variant.width(), variant.height())
+                    }
+                    _ => webp::Encoder::from_rgb(
+                        variant.as_bytes(),
+                        variant.width(),
+                        variant.height(),
+                    ),
+                };
+                fs::write(path, &*encoder.encode(WEBP_QUALITY))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn prune_dir(dir: &Path, keep: &HashSet<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            prune_dir(&path, keep);
+            // Only succeeds once the folder is empty
+            let _ = fs::remove_dir(&path);
+        } else if !keep.contains(&path) {
+            let _ = fs::remove_file(&path);
+        }
+    }
+}
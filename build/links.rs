@@ -0,0 +1,318 @@
+//! Dangling link and image checker for content.
+//!
+//! Every link and image in every `index.md` (drafts included) is resolved with
+//! the same rules as `render_markdown` and checked against the files under
+//! `public/` and the app's `Route` table. Shortcodes are checked against the
+//! registry, entry cards against the entries they name and `[[...]]` wiki links
+//! against the indexed entries. With the `orphaned-assets` feature, files in an
+//! entry folder that nothing references are listed as well.
+
+use crate::comment::COMMENTS_FILE;
+use crate::constants::COLLECTIONS;
+use crate::frontmatter::split_frontmatter;
+use crate::markdown::{extract_toc, options};
+use crate::path::{entry_file, is_entry_relative, is_external};
+use crate::shortcode::{is_shortcode, Shortcode};
+use crate::sitemap::STATIC_ROUTES;
+use crate::validate::Report;
+use crate::wikilink::LinkTargets;
+use pulldown_cmark::{Event, LinkType, Parser, Tag};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const PUBLIC_DIR: &str = "public";
+
+/// A link or image destination found in markdown.
+struct Reference {
+    dest: String,
+    line: usize,
+    is_image: bool,
+    /// A `[[...]]` link, naming an entry rather than a path.
+    is_wiki: bool,
+}
+
+/// Checks every entry's references, warning about targets that do not exist.
+/// `dynamic_routes` lists generated pages that have no content folder, such as `/series/{id}`,
+/// and `targets` the entries wiki links can name.
+pub fn check_links(
+    dynamic_routes: &[String],
+    targets: &LinkTargets,
+    report: &mut Report,
+    list_orphans: bool,
+) {
+    let mut referenced: HashSet<PathBuf> = HashSet::new();
+    let mut entry_dirs = Vec::new();
+
+    for content_type in COLLECTIONS.iter().map(|collection| collection.name) {
+        let Ok(dirs) = fs::read_dir(Path::new(PUBLIC_DIR).join("content").join(content_type))
+        else {
+            continue;
+        };
+        for dir in dirs.flatten().map(|entry| entry.path()) {
+            let md_path = dir.join("index.md");
+            let Ok(content) = fs::read_to_string(&md_path) else {
+                continue;
+            };
+            let id = dir.file_name().unwrap().to_string_lossy().to_string();
+            let (body, body_line) = match split_frontmatter(&content) {
+                Some(fm) => {
+                    if let Some(image) = frontmatter_image(fm.yaml) {
+                        referenced.insert(normalize(&dir.join(image.trim_start_matches("./"))));
+                    }
+                    let body_start = content.len() - fm.body.len();
+                    (fm.body, content[..body_start].matches('\n').count() + 1)
+                }
+                None => (content.as_str(), 1),
+            };
+
+            let headings: HashSet<String> = extract_toc(body)
+                .into_iter()
+                .map(|entry| entry.id)
+                .collect();
+            for reference in references(body, body_line) {
+                if reference.is_wiki {
+                    if let Err(message) = targets.resolve(&reference.dest) {
+                        report.warn(
+                            &md_path,
+                            Some(reference.line),
+                            format!("broken wiki link `[[{}]]`: {}", reference.dest, message),
+                        );
+                    }
+                    continue;
+                }
+                match resolve(&reference.dest, content_type, &id) {
+                    Target::External => {}
+                    Target::Section(section) => {
+                        if !headings.contains(&section) {
+                            report.warn(
+                                &md_path,
+                                Some(reference.line),
+                                format!(
+                                    "broken link `{}`: no heading with id `{}`",
+                                    reference.dest, section
+                                ),
+                            );
+                        }
+                    }
+                    Target::Route(path) if route_exists(&path, dynamic_routes) => {}
+                    Target::Route(path) | Target::File(path) => {
+                        let file = Path::new(PUBLIC_DIR).join(path.trim_start_matches('/'));
+                        if file.is_file() {
+                            referenced.insert(normalize(&file));
+                        } else {
+                            let kind = if reference.is_image { "image" } else { "link" };
+                            report.warn(
+                                &md_path,
+                                Some(reference.line),
+                                format!(
+                                    "broken {} `{}`: no route or file at `{}`",
+                                    kind, reference.dest, path
+                                ),
+                            );
+                        }
+                    }
+                }
+            }
+            for (source, line, top_level) in shortcodes(body, body_line) {
+                if let Err(message) = check_shortcode(source, top_level) {
+                    report.warn(&md_path, Some(line), message);
+                }
+            }
+            entry_dirs.push(dir);
+        }
+    }
+
+    if list_orphans {
+        for dir in entry_dirs {
+            for file in fs::read_dir(&dir).into_iter().flatten().flatten() {
+                let path = file.path();
+                if path.is_file()
+                    && path
+                        .file_name()
+                        .is_some_and(|name| name != "index.md" && name != COMMENTS_FILE)
+                    && !referenced.contains(&normalize(&path))
+                {
+                    report.warn(&path, None, "orphaned asset: nothing links to this file");
+                }
+            }
+        }
+    }
+}
+
+/// Where a markdown destination points once `render_markdown` has rewritten it.
+enum Target {
+    /// Another site or a scheme such as `mailto:`, left as is.
+    External,
+    /// A heading on the entry's own page (`#setup`).
+    Section(String),
+    /// A root-relative path, served by the router or as a file under `public/`.
+    Route(String),
+    /// A file relative to the entry folder, as `/content/{type}/{id}/...`.
+    File(String),
+}
+
+/// Like the renderer, anything without a URL scheme that does not start with
+/// `#` or `/` is taken to be a file in the entry folder.
+fn resolve(dest: &str, content_type: &str, id: &str) -> Target {
+    let path = decode(dest.split(['#', '?']).next().unwrap_or_default());
+    if is_external(dest) {
+        Target::External
+    } else if let Some(section) = dest.strip_prefix('#') {
+        Target::Section(decode(section))
+    } else if path.starts_with('/') {
+        Target::Route(path)
+    } else {
+        Target::File(format!("/{}", entry_file(content_type, id, &path)))
+    }
+}
+
+/// Whether a root-relative path matches a `Route` other than the catch-all.
+fn route_exists(path: &str, dynamic_routes: &[String]) -> bool {
+    let path = match path.trim_end_matches('/') {
+        "" => "/",
+        trimmed => trimmed,
+    };
+    if STATIC_ROUTES.iter().any(|(route, _)| *route == path)
+        || dynamic_routes.iter().any(|route| route == path)
+    {
+        return true;
+    }
+    // `/{route}` lists a collection and `/{route}/{id}` needs the entry's folder
+    COLLECTIONS.iter().any(|collection| {
+        let Some(rest) = path
+            .strip_prefix('/')
+            .and_then(|path| path.strip_prefix(collection.route))
+        else {
+            return false;
+        };
+        rest.is_empty()
+            || rest.strip_prefix('/').is_some_and(|id| {
+                !id.contains('/')
+                    && Path::new(PUBLIC_DIR)
+                        .join("content")
+                        .join(collection.name)
+                        .join(id)
+                        .join("index.md")
+                        .is_file()
+            })
+    })
+}
+
+/// Collects link and image destinations with their file line.
+fn references(body: &str, body_line: usize) -> Vec<Reference> {
+    Parser::new_ext(body, options())
+        .into_offset_iter()
+        .filter_map(|(event, range)| {
+            let (dest, is_image, is_wiki) = match event {
+                Event::Start(Tag::Link {
+                    link_type,
+                    dest_url,
+                    ..
+                }) => (
+                    dest_url,
+                    false,
+                    matches!(link_type, LinkType::WikiLink { .. }),
+                ),
+                Event::Start(Tag::Image { dest_url, .. }) => (dest_url, true, false),
+                _ => return None,
+            };
+            Some(Reference {
+                dest: dest.to_string(),
+                line: body_line + body[..range.start].matches('\n').count(),
+                is_image,
+                is_wiki,
+            })
+        })
+        .collect()
+}
+
+/// Shortcode paragraphs with their file line and whether they sit at the top
+/// level, the only place the renderer turns them into components.
+fn shortcodes(body: &str, body_line: usize) -> Vec<(&str, usize, bool)> {
+    let mut depth = 0usize;
+    let mut found = Vec::new();
+    for (event, range) in Parser::new_ext(body, options()).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Paragraph) => {
+                let source = body[range.clone()].trim();
+                if is_shortcode(source) {
+                    let line = body_line + body[..range.start].matches('\n').count();
+                    found.push((source, line, depth == 0));
+                }
+                depth += 1;
+            }
+            Event::Start(_) => depth += 1,
+            Event::End(_) => depth -= 1,
+            _ => {}
+        }
+    }
+    found
+}
+
+/// Why a shortcode would render as an error box (or as text), if it would.
+fn check_shortcode(source: &str, top_level: bool) -> Result<(), String> {
+    if !top_level {
+        return Err(format!(
+            "shortcode `{}` is inside a list, quote or callout and shows as text; put it on a top-level line",
+            source
+        ));
+    }
+    let shortcode =
+        Shortcode::parse(source).map_err(|e| format!("invalid shortcode `{}`: {}", source, e))?;
+    shortcode
+        .validate(COLLECTIONS)
+        .map_err(|e| format!("invalid shortcode `{}`: {}", source, e))?;
+    if let (Some(collection), Some(id)) =
+        (shortcode.entry_collection(COLLECTIONS), shortcode.arg("id"))
+    {
+        let md_path = Path::new(PUBLIC_DIR)
+            .join("content")
+            .join(collection.name)
+            .join(id)
+            .join("index.md");
+        if !md_path.is_file() {
+            return Err(format!(
+                "shortcode `{}`: no entry `{}` in {}",
+                source, id, collection.name
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The local `image_url` from a frontmatter block, if any.
+fn frontmatter_image(yaml: &str) -> Option<String> {
+    let value: serde_yaml::Value = serde_yaml::from_str(yaml).ok()?;
+    let image = value.get("image_url")?.as_str()?;
+    is_entry_relative(image).then(|| image.to_string())
+}
+
+/// Resolves `.` and `..` so the same file is always recorded under one path.
+fn normalize(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Decodes `%XX` escapes, as browsers do before requesting the file.
+fn decode(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
@@ -0,0 +1,286 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+mod backlinks;
+mod comments;
+// The app's settings; the build script reads only those about content and feeds
+#[allow(dead_code)]
+#[path = "../src/data/constants.rs"]
+mod constants;
+mod feeds;
+mod images;
+mod links;
+mod related;
+mod render;
+mod search;
+mod series_index;
+mod site;
+mod sitemap;
+mod tags;
+mod validate;
+
+use content_model::{
+    collection, comment, date, frontmatter, html, path, rendered, responsive, series, shortcode,
+    slug, taxonomy, tokenizer, wikilink,
+};
+use content_render::markdown;
+
+use collection::{Collection, EntryMeta, PublishStatus};
+use constants::COLLECTIONS;
+use date::Date;
+use images::ImagePipeline;
+use responsive::ResponsiveImage;
+use site::SiteConfig;
+use tags::Taxonomy;
+use validate::Report;
+
+/// An indexed entry together with its markdown body (frontmatter stripped).
+struct Entry {
+    meta: EntryMeta,
+    markdown: String,
+    /// Responsive variants of images in the body, keyed by their path inside the entry folder.
+    images: HashMap<String, ResponsiveImage>,
+}
+
+/// The indexed entries of one collection.
+struct Indexed {
+    collection: &'static Collection,
+    entries: Vec<Entry>,
+}
+
+fn main() {
+    for collection in COLLECTIONS {
+        println!("cargo:rerun-if-changed=public/content/{}", collection.name);
+    }
+    println!("cargo:rerun-if-changed={}", tags::TAXONOMY_PATH);
+    println!("cargo:rerun-if-changed=Dioxus.toml");
+    println!("cargo:rerun-if-env-changed=SITE_URL");
+
+    // `cargo build --features preview` keeps drafts and future-dated entries in the index.
+    let preview = std::env::var_os("CARGO_FEATURE_PREVIEW").is_some();
+    // `cargo build --features strict-content` turns content warnings into a failed build.
+    let strict = std::env::var_os("CARGO_FEATURE_STRICT_CONTENT").is_some();
+    // `cargo build --features orphaned-assets` also lists entry files nothing references.
+    let orphans = std::env::var_os("CARGO_FEATURE_ORPHANED_ASSETS").is_some();
+    let today = today();
+    let mut report = Report::default();
+    let site = SiteConfig::load();
+    // The app links to the feeds with the same absolute URLs the feeds use
+    println!("cargo:rustc-env=SITE_ROOT_URL={}", site.root_url());
+    let mut images = ImagePipeline::default();
+    let mut taxonomy = Taxonomy::load(&mut report);
+
+    let indexed: Vec<Indexed> = COLLECTIONS
+        .iter()
+        .map(|collection| Indexed {
+            collection,
+            entries: generate_index(
+                collection,
+                today,
+                preview,
+                &mut images,
+                &mut taxonomy,
+                &mut report,
+            ),
+        })
+        .collect();
+
+    let series = series_index::write_series_index(&indexed, &mut report);
+    let tags = tags::write_tags_index(&taxonomy, &indexed);
+    comments::write_comments(&indexed, &mut report);
+    let targets = backlinks::link_targets(&indexed);
+    feeds::write_feeds(&site, &indexed, &targets);
+    sitemap::write_sitemap(&site, &indexed, &series, &tags);
+    search::write_search_index(&indexed);
+    let related = related::compute_related(&indexed);
+    let backlinks = backlinks::compute_backlinks(&indexed, &targets);
+    for collection in &indexed {
+        render::write_rendered(&site, collection, &targets, &related, &backlinks);
+    }
+    images.prune();
+    // Last, so generated files such as `/feed.xml` can be linked to
+    let dynamic_routes: Vec<String> = series
+        .iter()
+        .map(|series| format!("/series/{}", series.id))
+        .chain(tags.iter().map(|tag| format!("/tags/{}", tag.slug)))
+        .collect();
+    links::check_links(&dynamic_routes, &targets, &mut report, orphans);
+
+    report.emit();
+    if strict && !report.is_empty() {
+        panic!(
+            "{} content problem(s) found; fix them or build without `strict-content`",
+            report.len()
+        );
+    }
+}
+
+/// Indexes every entry folder of a collection into `public/content/{name}_index.json`.
+fn generate_index(
+    collection: &Collection,
+    today: Date,
+    preview: bool,
+    images: &mut ImagePipeline,
+    taxonomy: &mut Taxonomy,
+    report: &mut Report,
+) -> Vec<Entry> {
+    let mut items = Vec::new();
+    let mut seen_ids: HashMap<String, PathBuf> = HashMap::new();
+    let dir_path = format!("public/content/{}", collection.name);
+    let dir = Path::new(&dir_path);
+
+    if dir.exists() && dir.is_dir() {
+        // Sorted so undeclared tags keep the same first spelling on every machine
+        let mut paths: Vec<PathBuf> = match fs::read_dir(dir) {
+            Ok(entries) => entries.flatten().map(|entry| entry.path()).collect(),
+            Err(e) => {
+                report.warn(dir, None, format!("cannot list the folder: {}", e));
+                Vec::new()
+            }
+        };
+        paths.sort();
+        for path in paths {
+            if path.is_dir() {
+                let md_path = path.join("index.md");
+                if !md_path.exists() {
+                    report.warn(&path, None, "content folder has no index.md and is skipped");
+                    continue;
+                }
+                let Some(id) = path.file_name().and_then(|name| name.to_str()) else {
+                    report.warn(
+                        &path,
+                        None,
+                        "folder name is not valid UTF-8 and cannot be an id; the entry is skipped",
+                    );
+                    continue;
+                };
+                let id = id.to_string();
+                // Folder names are URL segments, so ids differing only in case collide on most hosts
+                if let Some(other) = seen_ids.insert(id.to_lowercase(), path.clone()) {
+                    report.warn(
+                        &md_path,
+                        None,
+                        format!("duplicate id `{}` (also used by {})", id, other.display()),
+                    );
+                }
+                let content = match fs::read_to_string(&md_path) {
+                    Ok(content) => content,
+                    Err(e) => {
+                        report.warn(
+                            &md_path,
+                            Some(1),
+                            format!("cannot read the file ({}); the entry is skipped", e),
+                        );
+                        continue;
+                    }
+                };
+                if let Some(mut item) = parse_meta(&content, &id, collection, &md_path, report) {
+                    if resolve_status(&mut item.meta, today, preview) {
+                        item.meta.tags = taxonomy.canonicalize(&item.meta.tags);
+                        item.meta.cover = images.process(&item.meta.image_url, &md_path, report);
+                        item.images = images.process_markdown(
+                            &item.markdown,
+                            collection.name,
+                            &id,
+                            &md_path,
+                            report,
+                        );
+                        items.push(item);
+                    }
+                }
+            }
+        }
+    }
+
+    let metas: Vec<&EntryMeta> = items.iter().map(|item| &item.meta).collect();
+    write_if_changed(
+        format!("{}_index.json", dir_path),
+        &serde_json::to_string_pretty(&metas).unwrap(),
+    );
+    items
+}
+
+/// Writes generated output only when it differs, so unchanged files keep their mtime.
+fn write_if_changed(output_path: impl AsRef<Path>, content: &str) {
+    let output_path = output_path.as_ref();
+    let should_write = match fs::read_to_string(output_path) {
+        Ok(existing_content) => existing_content != content,
+        Err(_) => true,
+    };
+
+    if should_write {
+        if let Some(parent) = output_path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(output_path, content).unwrap();
+    }
+}
+
+/// Decides whether an entry belongs in the index for this build.
+/// Scheduled entries whose publish date has passed are promoted to `Published`.
+fn resolve_status(meta: &mut EntryMeta, today: Date, preview: bool) -> bool {
+    match meta.status {
+        PublishStatus::Published | PublishStatus::Unlisted => true,
+        PublishStatus::Draft => preview,
+        PublishStatus::Scheduled => {
+            if meta.publish_date() <= today {
+                meta.status = PublishStatus::Published;
+                true
+            } else {
+                preview
+            }
+        }
+    }
+}
+
+/// Today's date in the site timezone, comparable with frontmatter dates.
+fn today() -> Date {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    Date::from_unix(secs, constants::TIMEZONE_OFFSET_MINUTES)
+}
+
+fn parse_meta(
+    content: &str,
+    id: &str,
+    collection: &Collection,
+    md_path: &Path,
+    report: &mut Report,
+) -> Option<Entry> {
+    let Some(frontmatter) = frontmatter::split_frontmatter(content) else {
+        report.warn(
+            md_path,
+            Some(1),
+            "missing frontmatter block delimited by `---`",
+        );
+        return None;
+    };
+    let entry_dir = md_path.parent().unwrap_or(md_path);
+    if !validate::check_fields(&frontmatter, collection, entry_dir, md_path, report) {
+        return None;
+    }
+    // Undeclared keys were reported by `check_fields` and are dropped here
+    let meta = match EntryMeta::parse(
+        &frontmatter,
+        collection,
+        id,
+        constants::TIMEZONE_OFFSET_MINUTES,
+    ) {
+        Ok(meta) => meta,
+        Err(e) => {
+            let line = e.location().map(|loc| frontmatter.file_line(loc.line()));
+            report.warn(md_path, line, format!("invalid frontmatter: {}", e));
+            return None;
+        }
+    };
+
+    Some(Entry {
+        meta,
+        markdown: frontmatter.body.trim().to_string(),
+        images: HashMap::new(),
+    })
+}
@@ -0,0 +1,128 @@
+//! Related-content recommendations.
+//!
+//! Every indexed entry is scored against every listed one, across all
+//! collections, by tag overlap (Jaccard) and body similarity (TF-IDF cosine
+//! over the search tokenizer's terms). The best matches are embedded into each
+//! entry's rendered JSON, so posts and projects can recommend each other.
+
+use crate::rendered::RelatedEntry;
+use crate::search::extract_text;
+use crate::tokenizer::tokenize;
+use crate::{Collection, EntryMeta, Indexed, PublishStatus};
+use std::collections::{HashMap, HashSet};
+
+/// Entries recommended per page.
+const MAX_RELATED: usize = 3;
+/// Share of the score from tag overlap; the rest comes from text similarity.
+const TAG_WEIGHT: f32 = 0.5;
+/// Scores below this are coincidence rather than relatedness.
+const MIN_SCORE: f32 = 0.05;
+
+/// An entry reduced to what the ranking needs.
+struct Doc {
+    entry: RelatedEntry,
+    listed: bool,
+    tags: HashSet<String>,
+    vector: HashMap<String, f32>,
+}
+
+/// Related entries for every indexed entry, keyed by `{type}/{id}`.
+pub fn compute_related(indexed: &[Indexed]) -> HashMap<String, Vec<RelatedEntry>> {
+    let sources = indexed.iter().flat_map(|indexed| {
+        indexed.entries.iter().map(|item| {
+            let entry = related_entry(indexed.collection, &item.meta);
+            (entry, item.meta.status, &item.markdown)
+        })
+    });
+
+    let mut term_counts = Vec::new();
+    let mut document_frequency: HashMap<String, usize> = HashMap::new();
+    let mut docs: Vec<Doc> = Vec::new();
+    for (entry, status, markdown) in sources {
+        let (headings, body) = extract_text(markdown);
+        let mut counts: HashMap<String, u32> = HashMap::new();
+        for term in tokenize(&format!("{} {} {}", entry.title, headings, body)) {
+            *counts.entry(term).or_default() += 1;
+        }
+        for term in counts.keys() {
+            *document_frequency.entry(term.clone()).or_default() += 1;
+        }
+        term_counts.push(counts);
+        docs.push(Doc {
+            tags: entry.tags.iter().map(|tag| tag.to_lowercase()).collect(),
+            listed: status != PublishStatus::Unlisted,
+            entry,
+            vector: HashMap::new(),
+        });
+    }
+
+    let total = docs.len() as f32;
+    for (doc, counts) in docs.iter_mut().zip(term_counts) {
+        doc.vector = counts
+            .into_iter()
+            .map(|(term, count)| {
+                // Smoothed IDF keeps terms shared by every entry from vanishing on tiny sites
+                let idf = (1.0 + total / document_frequency[&term] as f32).ln();
+                (term, (1.0 + (count as f32).ln()) * idf)
+            })
+            .collect();
+        let norm = doc.vector.values().map(|w| w * w).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            doc.vector.values_mut().for_each(|w| *w /= norm);
+        }
+    }
+
+    docs.iter()
+        .enumerate()
+        .map(|(i, doc)| {
+            let mut ranked: Vec<(f32, &Doc)> = docs
+                .iter()
+                .enumerate()
+                .filter(|(j, other)| *j != i && other.listed)
+                .map(|(_, other)| (score(doc, other), other))
+                .filter(|(score, _)| *score >= MIN_SCORE)
+                .collect();
+            ranked.sort_by(|a, b| {
+                b.0.total_cmp(&a.0)
+                    .then_with(|| a.1.entry.id.cmp(&b.1.entry.id))
+            });
+            let related = ranked
+                .into_iter()
+                .take(MAX_RELATED)
+                .map(|(_, other)| other.entry.clone())
+                .collect();
+            (
+                format!("{}/{}", doc.entry.content_type, doc.entry.id),
+                related,
+            )
+        })
+        .collect()
+}
+
+/// What a card linking to an entry needs.
+pub fn related_entry(collection: &Collection, meta: &EntryMeta) -> RelatedEntry {
+    RelatedEntry {
+        content_type: collection.name.to_string(),
+        id: meta.id.clone(),
+        title: meta.title.clone(),
+        description: meta.description.clone(),
+        image_url: meta.image_url.clone(),
+        cover: meta.cover.clone(),
+        tags: meta.tags.clone(),
+    }
+}
+
+fn score(a: &Doc, b: &Doc) -> f32 {
+    let union = a.tags.union(&b.tags).count();
+    let tags = if union == 0 {
+        0.0
+    } else {
+        a.tags.intersection(&b.tags).count() as f32 / union as f32
+    };
+    let text: f32 = a
+        .vector
+        .iter()
+        .filter_map(|(term, weight)| Some(weight * b.vector.get(term)?))
+        .sum();
+    TAG_WEIGHT * tags + (1.0 - TAG_WEIGHT) * text
+}
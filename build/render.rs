@@ -0,0 +1,64 @@
+//! Pre-renders every indexed entry to `public/content/rendered/{type}/{id}.json`,
+//! so detail pages only fetch and display a document tree instead of parsing markdown in wasm.
+
+use crate::constants::CONTENT_POLICY;
+use crate::markdown::{extract_toc, render_document, word_count};
+use crate::rendered::{RelatedEntry, RenderedContent};
+use crate::site::SiteConfig;
+use crate::wikilink::LinkTargets;
+use crate::{write_if_changed, Indexed};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+const OUTPUT_DIR: &str = "public/content/rendered";
+
+pub fn write_rendered(
+    site: &SiteConfig,
+    indexed: &Indexed,
+    targets: &LinkTargets,
+    related: &HashMap<String, Vec<RelatedEntry>>,
+    backlinks: &HashMap<String, Vec<RelatedEntry>>,
+) {
+    let collection = indexed.collection;
+    let content_type = collection.name;
+    let dir = Path::new(OUTPUT_DIR).join(content_type);
+    let mut written = HashSet::new();
+
+    for entry in &indexed.entries {
+        let id = &entry.meta.id;
+        let key = format!("{}/{}", content_type, id);
+        let rendered = RenderedContent {
+            meta: &entry.meta,
+            // Same link/image rewriting as the runtime, against the deploy-time base path
+            body: render_document(
+                &entry.markdown,
+                id,
+                collection,
+                &site.base_path,
+                &entry.images,
+                targets,
+                &CONTENT_POLICY,
+            ),
+            toc: extract_toc(&entry.markdown),
+            word_count: word_count(&entry.markdown),
+            related: related.get(&key).cloned().unwrap_or_default(),
+            backlinks: backlinks.get(&key).cloned().unwrap_or_default(),
+        };
+        let file_name = format!("{}.json", id);
+        write_if_changed(
+            dir.join(&file_name),
+            &serde_json::to_string(&rendered).unwrap(),
+        );
+        written.insert(file_name);
+    }
+
+    // Drop output for entries that were removed or are no longer indexed (e.g. back to draft)
+    if let Ok(existing) = fs::read_dir(&dir) {
+        for file in existing.flatten() {
+            if !written.contains(file.file_name().to_string_lossy().as_ref()) {
+                let _ = fs::remove_file(file.path());
+            }
+        }
+    }
+}
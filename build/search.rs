@@ -0,0 +1,67 @@
+//! Full-text search index generation.
+//!
+//! Writes `public/content/search_index.json`, the [`SearchIndex`] the app
+//! queries, over the title, tags, headings and body text of every listed entry.
+
+use crate::{write_if_changed, Indexed, PublishStatus};
+use content_model::search::{SearchIndex, BODY_WEIGHT, HEADING_WEIGHT, TAG_WEIGHT, TITLE_WEIGHT};
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+
+const OUTPUT_PATH: &str = "public/content/search_index.json";
+
+/// Builds the search index over every listed entry of every collection.
+pub fn write_search_index(indexed: &[Indexed]) {
+    let mut index = SearchIndex::default();
+    for collection in indexed {
+        for entry in &collection.entries {
+            if entry.meta.status == PublishStatus::Unlisted {
+                continue;
+            }
+            let (headings, body) = extract_text(&entry.markdown);
+            let tags = entry.meta.tags.join(" ");
+            let fields = [
+                (entry.meta.title.as_str(), TITLE_WEIGHT),
+                (tags.as_str(), TAG_WEIGHT),
+                (headings.as_str(), HEADING_WEIGHT),
+                (body.as_str(), BODY_WEIGHT),
+            ];
+            index.add(collection.collection.name, &entry.meta.id, &fields, &body);
+        }
+    }
+
+    write_if_changed(OUTPUT_PATH, &serde_json::to_string(&index).unwrap());
+}
+
+/// Splits markdown into heading text and plain body text.
+pub fn extract_text(markdown: &str) -> (String, String) {
+    let mut headings = String::new();
+    let mut body = String::new();
+    let mut in_heading = false;
+
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Start(Tag::Heading { .. }) => in_heading = true,
+            Event::End(TagEnd::Heading(_)) => {
+                in_heading = false;
+                headings.push(' ');
+            }
+            Event::Text(text) | Event::Code(text) => {
+                let target = if in_heading { &mut headings } else { &mut body };
+                target.push_str(&text);
+            }
+            // Inline markup must not split words; block ends and breaks separate them
+            Event::End(
+                TagEnd::Emphasis
+                | TagEnd::Strong
+                | TagEnd::Strikethrough
+                | TagEnd::Link
+                | TagEnd::Image,
+            ) => {}
+            Event::SoftBreak | Event::HardBreak | Event::End(_) => body.push(' '),
+            _ => {}
+        }
+    }
+
+    let body = body.split_whitespace().collect::<Vec<_>>().join(" ");
+    (headings, body)
+}
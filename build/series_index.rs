@@ -0,0 +1,87 @@
+//! Series index generation.
+//!
+//! Groups listed entries of every collection by their `series` frontmatter
+//! and writes `public/content/series_index.json`, one entry per series with
+//! its parts ordered by `part`.
+
+use crate::series::{Series, SeriesPart};
+use crate::validate::Report;
+use crate::{write_if_changed, EntryMeta, Indexed};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const OUTPUT_PATH: &str = "public/content/series_index.json";
+
+/// Writes the series index and returns it for the sitemap and link checks.
+pub fn write_series_index(indexed: &[Indexed], report: &mut Report) -> Vec<Series> {
+    let mut grouped: BTreeMap<&str, Vec<(&str, &EntryMeta)>> = BTreeMap::new();
+    for indexed in indexed {
+        for entry in &indexed.entries {
+            // Series navigation and `/series/{id}` would reveal an unlisted part
+            if !entry.meta.status.is_listed() {
+                continue;
+            }
+            if let Some(series) = &entry.meta.series {
+                grouped
+                    .entry(&series.id)
+                    .or_default()
+                    .push((indexed.collection.name, &entry.meta));
+            }
+        }
+    }
+
+    let series: Vec<Series> = grouped
+        .into_iter()
+        .map(|(id, mut metas)| {
+            metas.sort_by_key(|(_, meta)| (part_of(meta), meta.date));
+            for pair in metas.windows(2) {
+                let ((_, prev), (collection, meta)) = (pair[0], pair[1]);
+                if part_of(prev) == part_of(meta) {
+                    report.warn(
+                        &md_path(collection, &meta.id),
+                        None,
+                        format!(
+                            "series `{}` part {} is also used by `{}`",
+                            id,
+                            part_of(meta),
+                            prev.id
+                        ),
+                    );
+                }
+            }
+            let title = metas
+                .iter()
+                .find_map(|(_, meta)| meta.series.as_ref()?.title.clone())
+                .unwrap_or_else(|| id.to_string());
+            Series {
+                id: id.to_string(),
+                title,
+                parts: metas
+                    .iter()
+                    .map(|(collection, meta)| SeriesPart {
+                        collection: collection.to_string(),
+                        id: meta.id.clone(),
+                        title: meta.title.clone(),
+                        description: meta.description.clone(),
+                        date: meta.date,
+                        part: part_of(meta),
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    write_if_changed(OUTPUT_PATH, &serde_json::to_string_pretty(&series).unwrap());
+    series
+}
+
+fn part_of(meta: &EntryMeta) -> u32 {
+    meta.series.as_ref().map_or(0, |series| series.part)
+}
+
+fn md_path(collection: &str, id: &str) -> PathBuf {
+    Path::new("public/content")
+        .join(collection)
+        .join(id)
+        .join("index.md")
+}
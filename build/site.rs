@@ -0,0 +1,68 @@
+//! Site-wide settings the build script needs to produce absolute URLs.
+
+use crate::constants;
+use std::fs;
+
+pub struct SiteConfig {
+    /// Origin such as `https://user.github.io`, without a trailing slash.
+    pub site_url: String,
+    /// GitHub Pages sub-path such as `/repo_name`, or `""` when served from the root.
+    /// Mirrors what `get_base_path` reads from the `<base>` tag at runtime.
+    pub base_path: String,
+}
+
+impl SiteConfig {
+    /// Reads `SITE_URL` (falling back to `constants::SITE_URL`) and the
+    /// `base_path` from `Dioxus.toml`, which CI rewrites to the repository name.
+    pub fn load() -> Self {
+        let site_url =
+            std::env::var("SITE_URL").unwrap_or_else(|_| constants::SITE_URL.to_string());
+        Self {
+            site_url: site_url.trim_end_matches('/').to_string(),
+            base_path: read_base_path(),
+        }
+    }
+
+    /// Absolute URL of the site root, e.g. `https://user.github.io/repo_name`.
+    pub fn root_url(&self) -> String {
+        format!("{}{}", self.site_url, self.base_path)
+    }
+
+    /// Absolute URL for a root-relative `path` such as `/blog/my-post`.
+    pub fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.root_url(), path.trim_start_matches('/'))
+    }
+
+    /// Absolute URL for an indexed `image_url` (already normalized by `parse_meta`).
+    pub fn asset_url(&self, path: &str) -> String {
+        if path.starts_with("http") {
+            path.to_string()
+        } else {
+            self.url(path)
+        }
+    }
+}
+
+fn read_base_path() -> String {
+    let Ok(config) = fs::read_to_string("Dioxus.toml") else {
+        return String::new();
+    };
+    let base_path = config
+        .parse::<toml::Table>()
+        .ok()
+        .and_then(|table| {
+            table
+                .get("web")?
+                .get("app")?
+                .get("base_path")?
+                .as_str()
+                .map(str::to_string)
+        })
+        .unwrap_or_default();
+    let trimmed = base_path.trim_matches('/');
+    if trimmed.is_empty() {
+        String::new()
+    } else {
+        format!("/{}", trimmed)
+    }
+}
@@ -0,0 +1,130 @@
+//! `sitemap.xml` and `robots.txt` generation.
+//!
+//! Covers every `Route` variant in `src/main.rs`: the static pages, each
+//! collection's list page, plus one URL per published entry, series and tag.
+//! Drafts, scheduled and unlisted entries never appear.
+
+use crate::date::Date;
+use crate::html::escape;
+use crate::series::Series;
+use crate::site::SiteConfig;
+use crate::taxonomy::TagInfo;
+use crate::{write_if_changed, EntryMeta, Indexed, PublishStatus};
+
+/// Which entries date a static page's `lastmod`.
+pub enum DatedBy {
+    Everything,
+    Nothing,
+}
+
+/// Static routes of the app (keep in sync with `Route`). Collection list pages
+/// are added from `COLLECTIONS`.
+pub const STATIC_ROUTES: &[(&str, DatedBy)] = &[
+    ("/", DatedBy::Everything),
+    ("/tags", DatedBy::Everything),
+    ("/about", DatedBy::Nothing),
+    ("/contact", DatedBy::Nothing),
+];
+
+struct UrlEntry {
+    loc: String,
+    lastmod: Option<Date>,
+}
+
+pub fn write_sitemap(site: &SiteConfig, indexed: &[Indexed], series: &[Series], tags: &[TagInfo]) {
+    // Published entries with their collection
+    let published: Vec<(&str, &str, &EntryMeta)> = indexed
+        .iter()
+        .flat_map(|indexed| {
+            indexed.entries.iter().map(|entry| {
+                (
+                    indexed.collection.name,
+                    indexed.collection.route,
+                    &entry.meta,
+                )
+            })
+        })
+        .filter(|(_, _, meta)| meta.status == PublishStatus::Published)
+        .collect();
+    let newest = published.iter().map(|(_, _, meta)| meta.date).max();
+
+    let mut urls: Vec<UrlEntry> = STATIC_ROUTES
+        .iter()
+        .map(|(path, dated_by)| UrlEntry {
+            loc: site.url(path),
+            lastmod: match dated_by {
+                DatedBy::Everything => newest,
+                DatedBy::Nothing => None,
+            },
+        })
+        .collect();
+    urls.extend(indexed.iter().map(|indexed| {
+        UrlEntry {
+            loc: site.url(&format!("/{}", indexed.collection.route)),
+            lastmod: published
+                .iter()
+                .filter(|(name, _, _)| *name == indexed.collection.name)
+                .map(|(_, _, meta)| meta.date)
+                .max(),
+        }
+    }));
+    let fixed = urls.len();
+    urls.extend(published.iter().map(|(_, route, meta)| UrlEntry {
+        loc: site.url(&format!("/{}/{}", route, meta.id)),
+        lastmod: Some(meta.date),
+    }));
+    urls.extend(series.iter().filter_map(|series| {
+        // A series page is only worth indexing once one of its parts is public
+        let lastmod = series
+            .parts
+            .iter()
+            .filter(|part| {
+                published
+                    .iter()
+                    .any(|(name, _, meta)| *name == part.collection && meta.id == part.id)
+            })
+            .map(|part| part.date)
+            .max()?;
+        Some(UrlEntry {
+            loc: site.url(&format!("/series/{}", series.id)),
+            lastmod: Some(lastmod),
+        })
+    }));
+    urls.extend(tags.iter().filter_map(|tag| {
+        let lastmod = published
+            .iter()
+            .filter(|(name, _, meta)| tag.entries_in(name).contains(&meta.id))
+            .map(|(_, _, meta)| meta.date)
+            .max()?;
+        Some(UrlEntry {
+            loc: site.url(&format!("/tags/{}", tag.slug)),
+            lastmod: Some(lastmod),
+        })
+    }));
+    // Stable order keeps the file unchanged between identical builds
+    urls[fixed..].sort_by(|a, b| a.loc.cmp(&b.loc));
+
+    let body: String = urls
+        .iter()
+        .map(|url| match &url.lastmod {
+            Some(date) => format!(
+                "  <url>\n    <loc>{}</loc>\n    <lastmod>{}</lastmod>\n  </url>\n",
+                escape(&url.loc),
+                date
+            ),
+            None => format!("  <url>\n    <loc>{}</loc>\n  </url>\n", escape(&url.loc)),
+        })
+        .collect();
+    let sitemap = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n{}</urlset>\n",
+        body
+    );
+    write_if_changed("public/sitemap.xml", &sitemap);
+
+    let robots = format!(
+        "User-agent: *\nAllow: {}/\n\nSitemap: {}\n",
+        site.base_path,
+        site.url("/sitemap.xml")
+    );
+    write_if_changed("public/robots.txt", &robots);
+}
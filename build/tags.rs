@@ -0,0 +1,149 @@
+//! Tag taxonomy and tag index generation.
+//!
+//! `public/content/tags.yaml` declares canonical tag names with their aliases,
+//! descriptions and colors. Every entry's tags are rewritten to the canonical
+//! name while indexing, so `rust`, `Rust` and `rust-lang` all end up as one
+//! tag. Tags missing from the file keep the first spelling seen.
+//!
+//! ```yaml
+//! Rust:
+//!   aliases: [rust-lang, rustlang]
+//!   description: Systems programming without the footguns.
+//!   color: "#dea584"
+//! ```
+
+use crate::slug::slugify;
+use crate::taxonomy::TagInfo;
+use crate::validate::Report;
+use crate::{write_if_changed, Indexed, PublishStatus};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+pub const TAXONOMY_PATH: &str = "public/content/tags.yaml";
+const OUTPUT_PATH: &str = "public/content/tags_index.json";
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct TagDefinition {
+    aliases: Vec<String>,
+    description: Option<String>,
+    color: Option<String>,
+}
+
+/// Canonical tags by slug, plus the slug every known spelling resolves to.
+#[derive(Default)]
+pub struct Taxonomy {
+    tags: BTreeMap<String, TagInfo>,
+    aliases: HashMap<String, String>,
+}
+
+impl Taxonomy {
+    /// Reads `tags.yaml`; a missing file means every tag is used as written.
+    pub fn load(report: &mut Report) -> Self {
+        let mut taxonomy = Self::default();
+        let path = Path::new(TAXONOMY_PATH);
+        let Ok(content) = fs::read_to_string(path) else {
+            return taxonomy;
+        };
+        let definitions: BTreeMap<String, Option<TagDefinition>> =
+            match serde_yaml::from_str(&content) {
+                Ok(definitions) => definitions,
+                Err(e) => {
+                    let line = e.location().map(|loc| loc.line());
+                    report.warn(path, line, format!("invalid tag taxonomy: {}", e));
+                    return taxonomy;
+                }
+            };
+
+        for (name, definition) in definitions {
+            let definition = definition.unwrap_or_default();
+            let slug = slugify(&name);
+            for spelling in std::iter::once(&name).chain(&definition.aliases) {
+                if let Some(other) = taxonomy.aliases.insert(slugify(spelling), slug.clone()) {
+                    if other != slug {
+                        report.warn(
+                            path,
+                            None,
+                            format!(
+                                "tag `{}` is claimed by both `{}` and `{}`",
+                                spelling, other, slug
+                            ),
+                        );
+                    }
+                }
+            }
+            taxonomy.tags.insert(
+                slug.clone(),
+                TagInfo {
+                    slug,
+                    name,
+                    description: definition.description,
+                    color: definition.color,
+                    entries: BTreeMap::new(),
+                },
+            );
+        }
+        taxonomy
+    }
+
+    /// Rewrites tags to their canonical names, dropping duplicates.
+    pub fn canonicalize(&mut self, tags: &[String]) -> Vec<String> {
+        let mut canonical: Vec<String> = Vec::new();
+        for tag in tags {
+            let slug = slugify(tag);
+            if slug.is_empty() {
+                continue;
+            }
+            let slug = self.aliases.entry(slug.clone()).or_insert(slug).clone();
+            let name = &self
+                .tags
+                .entry(slug.clone())
+                .or_insert_with(|| TagInfo {
+                    slug,
+                    name: tag.trim().to_string(),
+                    description: None,
+                    color: None,
+                    entries: BTreeMap::new(),
+                })
+                .name;
+            if !canonical.contains(name) {
+                canonical.push(name.clone());
+            }
+        }
+        canonical
+    }
+}
+
+/// Writes every tag used by a listed entry, with the entries using it.
+pub fn write_tags_index(taxonomy: &Taxonomy, indexed: &[Indexed]) -> Vec<TagInfo> {
+    let mut tags = taxonomy.tags.clone();
+    for indexed in indexed {
+        for entry in indexed
+            .entries
+            .iter()
+            .filter(|entry| entry.meta.status != PublishStatus::Unlisted)
+        {
+            for tag in &entry.meta.tags {
+                if let Some(info) = tags.get_mut(&slugify(tag)) {
+                    info.entries
+                        .entry(indexed.collection.name.to_string())
+                        .or_default()
+                        .push(entry.meta.id.clone());
+                }
+            }
+        }
+    }
+
+    let index: Vec<TagInfo> = tags
+        .into_values()
+        .filter(|info| info.count() > 0)
+        .map(|mut info| {
+            info.entries.values_mut().for_each(|ids| ids.sort());
+            info
+        })
+        .collect();
+    write_if_changed(OUTPUT_PATH, &serde_json::to_string_pretty(&index).unwrap());
+    index
+}
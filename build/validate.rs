@@ -0,0 +1,244 @@
+//! Frontmatter validation for the content indexer.
+//!
+//! Problems are collected into a [`Report`] and surfaced as `cargo:warning`
+//! lines pointing at `file:line`, so a broken post is never dropped silently.
+
+use crate::collection::{Collection, EntryMeta, FieldKind};
+use crate::constants::TIMEZONE_OFFSET_MINUTES;
+use crate::date::Date;
+use crate::frontmatter::Frontmatter;
+use crate::path::is_entry_relative;
+use serde_yaml::{Mapping, Value};
+use std::path::{Path, PathBuf};
+
+/// Fields every entry must declare in its frontmatter, whatever its collection.
+pub const REQUIRED_FIELDS: &[&str] = &[
+    "title",
+    "date",
+    "author",
+    "description",
+    "image_url",
+    "tags",
+];
+
+/// Fields holding a date, in any format [`Date::parse`] accepts.
+const DATE_FIELDS: &[&str] = &["date", "publish_date"];
+
+pub struct Diagnostic {
+    path: PathBuf,
+    line: Option<usize>,
+    message: String,
+}
+
+/// Collects content problems found while indexing.
+#[derive(Default)]
+pub struct Report {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Report {
+    pub fn warn(&mut self, path: &Path, line: Option<usize>, message: impl Into<String>) {
+        self.diagnostics.push(Diagnostic {
+            path: path.to_path_buf(),
+            line,
+            message: message.into(),
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.diagnostics.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// Prints every diagnostic as a cargo warning.
+    pub fn emit(&self) {
+        for d in &self.diagnostics {
+            match d.line {
+                Some(line) => {
+                    println!("cargo:warning={}:{}: {}", d.path.display(), line, d.message)
+                }
+                None => println!("cargo:warning={}: {}", d.path.display(), d.message),
+            }
+        }
+    }
+}
+
+/// Checks required fields, dates, the collection's own fields and local images.
+/// Returns `false` when the entry is too broken to index.
+pub fn check_fields(
+    fm: &Frontmatter,
+    collection: &Collection,
+    entry_dir: &Path,
+    md_path: &Path,
+    report: &mut Report,
+) -> bool {
+    let mapping: Mapping = match serde_yaml::from_str(fm.yaml) {
+        Ok(Value::Mapping(mapping)) => mapping,
+        Ok(_) => {
+            report.warn(
+                md_path,
+                Some(fm.base_line),
+                "frontmatter is not a key/value mapping",
+            );
+            return false;
+        }
+        Err(e) => {
+            let line = e.location().map(|loc| fm.file_line(loc.line()));
+            report.warn(
+                md_path,
+                line.or(Some(fm.base_line)),
+                format!("invalid YAML: {}", e),
+            );
+            return false;
+        }
+    };
+
+    let mut indexable = true;
+    for field in REQUIRED_FIELDS {
+        if !mapping.contains_key(*field) {
+            report.warn(
+                md_path,
+                Some(fm.base_line),
+                format!("missing required field `{}`", field),
+            );
+            indexable = false;
+        }
+    }
+
+    for field in DATE_FIELDS {
+        if let Some(value) = mapping.get(*field) {
+            let valid = value
+                .as_str()
+                .and_then(|text| Date::parse(text, TIMEZONE_OFFSET_MINUTES))
+                .is_some();
+            if !valid {
+                report.warn(
+                    md_path,
+                    fm.key_line(field),
+                    format!(
+                        "invalid {} `{}`, expected a date such as 2026-01-05 or Jan 5, 2026",
+                        field,
+                        display_value(value)
+                    ),
+                );
+                indexable = false;
+            }
+        }
+    }
+
+    for field in collection.fields {
+        match mapping.get(field.name) {
+            None if field.required => {
+                report.warn(
+                    md_path,
+                    Some(fm.base_line),
+                    format!("missing required field `{}`", field.name),
+                );
+                indexable = false;
+            }
+            None => {}
+            Some(value) => {
+                if let Some(expected) = type_error(&field.kind, value) {
+                    report.warn(
+                        md_path,
+                        fm.key_line(field.name),
+                        format!(
+                            "invalid {} `{}`, expected {}",
+                            field.name,
+                            display_value(value),
+                            expected
+                        ),
+                    );
+                    indexable = false;
+                }
+            }
+        }
+    }
+
+    for key in mapping.keys().filter_map(Value::as_str) {
+        if !EntryMeta::COMMON_FIELDS.contains(&key)
+            && !EntryMeta::DEPRECATED_FIELDS.contains(&key)
+            && collection.field(key).is_none()
+        {
+            report.warn(
+                md_path,
+                fm.key_line(key),
+                format!(
+                    "unknown field `{}` is ignored; declare it in the `{}` collection's fields",
+                    key, collection.name
+                ),
+            );
+        }
+    }
+
+    if let Some(image) = mapping.get("image_url").and_then(Value::as_str) {
+        if is_entry_relative(image) {
+            let local = entry_dir.join(image.trim_start_matches("./"));
+            if !local.is_file() {
+                report.warn(
+                    md_path,
+                    fm.key_line("image_url"),
+                    format!("image `{}` not found in {}", image, entry_dir.display()),
+                );
+            }
+        }
+    }
+
+    if let Some(declared) = mapping.get("id").and_then(Value::as_str) {
+        let folder_id = entry_dir
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+        if declared != folder_id {
+            report.warn(
+                md_path,
+                fm.key_line("id"),
+                format!(
+                    "frontmatter id `{}` is ignored, the folder name `{}` is used",
+                    declared, folder_id
+                ),
+            );
+        }
+    }
+
+    indexable
+}
+
+/// What a value of `kind` should look like, if `value` is not one.
+fn type_error(kind: &FieldKind, value: &Value) -> Option<&'static str> {
+    let valid = match kind {
+        FieldKind::Text => value.is_string(),
+        FieldKind::Number => value.is_number(),
+        FieldKind::Bool => value.is_bool(),
+        FieldKind::Date => value
+            .as_str()
+            .and_then(|text| Date::parse(text, TIMEZONE_OFFSET_MINUTES))
+            .is_some(),
+        FieldKind::List => value
+            .as_sequence()
+            .is_some_and(|items| items.iter().all(Value::is_string)),
+        FieldKind::Link { .. } => value.as_str().is_some_and(|url| !is_entry_relative(url)),
+    };
+    let expected = match kind {
+        FieldKind::Text => "text",
+        FieldKind::Number => "a number",
+        FieldKind::Bool => "true or false",
+        FieldKind::Date => "a date such as 2026-01-05 or Jan 5, 2026",
+        FieldKind::List => "a list of text",
+        FieldKind::Link { .. } => "an http(s) or root-relative URL",
+    };
+    (!valid).then_some(expected)
+}
+
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => serde_yaml::to_string(other)
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+    }
+}
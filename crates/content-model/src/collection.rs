@@ -0,0 +1,352 @@
+//! Content collections: folders of `index.md` entries such as posts and projects.
+//!
+//! Collections are declared in `COLLECTIONS` in the app's `constants.rs`; the
+//! indexer, fetchers and views all work off these definitions, so a new kind of
+//! content (talks, notes, ...) only needs an entry there and a folder under
+//! `public/content/`.
+
+use crate::date::Date;
+#[cfg(feature = "yaml")]
+use crate::frontmatter::Frontmatter;
+use crate::path::resolve_image_url;
+use crate::responsive::ResponsiveImage;
+use crate::series::SeriesRef;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A kind of content, listed at `/{route}` with entries at `/{route}/{id}`.
+#[derive(Debug, PartialEq)]
+pub struct Collection {
+    /// Folder under `public/content/`, also the key of its indexes (`posts_index.json`).
+    pub name: &'static str,
+    /// How references name one entry: the `{{< post id="…" >}}` shortcode and
+    /// the `[[post:id]]` wiki link prefix. Never displayed, so relabelling
+    /// `item` leaves existing references working.
+    pub singular: &'static str,
+    /// URL segment of the list and detail pages.
+    pub route: &'static str,
+    /// Navigation and breadcrumb label.
+    pub label: &'static str,
+    /// Heading and subtitle of the list page.
+    pub title: &'static str,
+    pub subtitle: &'static str,
+    /// What a single entry is called, as in "Post Not Found".
+    pub item: &'static str,
+    pub search_placeholder: &'static str,
+    /// Frontmatter fields specific to this collection.
+    pub fields: &'static [Field],
+    /// Whether entries go into the RSS, Atom and JSON feeds.
+    pub feed: bool,
+    /// Whether detail pages end with share buttons.
+    pub share: bool,
+    /// Whether detail pages end with the "Get in Touch" call to action.
+    pub call_to_action: bool,
+}
+
+/// An extra frontmatter field, type-checked while indexing.
+#[derive(Debug, PartialEq)]
+pub struct Field {
+    pub name: &'static str,
+    pub kind: FieldKind,
+    pub required: bool,
+    /// Caption on the detail page; fields without one are not displayed.
+    pub label: Option<&'static str>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FieldKind {
+    Text,
+    Number,
+    Bool,
+    /// Any format [`Date::parse`] accepts, stored as `YYYY-MM-DD`.
+    Date,
+    /// A list of strings.
+    List,
+    /// An absolute or root-relative URL, shown as a button. Its text is taken
+    /// from the field named `text_field` when the entry sets it, else the label.
+    Link {
+        text_field: Option<&'static str>,
+    },
+}
+
+impl Collection {
+    /// The collection of `collections` stored in `public/content/{name}`.
+    pub fn by_name<'a>(collections: &'a [Collection], name: &str) -> Option<&'a Collection> {
+        collections
+            .iter()
+            .find(|collection| collection.name == name)
+    }
+
+    /// The collection of `collections` served at `/{route}`.
+    pub fn by_route<'a>(collections: &'a [Collection], route: &str) -> Option<&'a Collection> {
+        collections
+            .iter()
+            .find(|collection| collection.route == route)
+    }
+
+    pub fn field(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+/// Posts and projects as the template declares them, for tests that resolve collections.
+#[cfg(test)]
+pub(crate) const TEST_COLLECTIONS: &[Collection] = &[
+    Collection {
+        name: "posts",
+        singular: "post",
+        route: "blog",
+        label: "Blog",
+        title: "Blog",
+        subtitle: "",
+        item: "Post",
+        search_placeholder: "",
+        fields: &[],
+        feed: true,
+        share: true,
+        call_to_action: false,
+    },
+    Collection {
+        name: "projects",
+        singular: "project",
+        route: "projects",
+        label: "Projects",
+        title: "Projects",
+        subtitle: "",
+        item: "Project",
+        search_placeholder: "",
+        fields: &[],
+        feed: false,
+        share: false,
+        call_to_action: true,
+    },
+];
+
+/// Publication state of an entry, set via `status:` in frontmatter.
+/// Drafts and future scheduled entries only reach the index in `preview` builds.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PublishStatus {
+    #[default]
+    Published,
+    Draft,
+    Scheduled,
+    Unlisted,
+}
+
+impl PublishStatus {
+    /// Whether the entry should appear in galleries and on the home page.
+    pub fn is_listed(&self) -> bool {
+        *self != PublishStatus::Unlisted
+    }
+
+    /// Whether the entry is not yet public and should carry a "Draft" badge.
+    pub fn is_draft(&self) -> bool {
+        matches!(self, PublishStatus::Draft | PublishStatus::Scheduled)
+    }
+}
+
+/// Frontmatter of an entry in any collection, as stored in `{name}_index.json`.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct EntryMeta {
+    #[serde(default)]
+    pub id: String,
+    pub title: String,
+    pub date: Date,
+    pub author: String,
+    pub description: String,
+    pub image_url: String,
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub series: Option<SeriesRef>,
+    #[serde(default)]
+    pub status: PublishStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publish_date: Option<Date>,
+    /// Responsive variants of `image_url`, generated at build time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover: Option<ResponsiveImage>,
+    /// Values of the collection's [`Field`]s.
+    #[serde(flatten)]
+    pub fields: BTreeMap<String, serde_json::Value>,
+}
+
+impl EntryMeta {
+    /// Frontmatter keys every collection understands.
+    pub const COMMON_FIELDS: &'static [&'static str] = &[
+        "id",
+        "title",
+        "date",
+        "author",
+        "description",
+        "image_url",
+        "tags",
+        "series",
+        "status",
+        "publish_date",
+    ];
+
+    /// Frontmatter keys older versions of the template wrote, still accepted
+    /// without a warning and ignored.
+    pub const DEPRECATED_FIELDS: &'static [&'static str] = &["route"];
+
+    /// When a scheduled entry goes live: `publish_date`, or `date` if omitted.
+    pub fn publish_date(&self) -> Date {
+        self.publish_date.unwrap_or(self.date)
+    }
+
+    /// Deserializes an entry's frontmatter and [normalizes](EntryMeta::normalize) it.
+    /// Times with an offset are dated in the timezone `offset_minutes` east of UTC.
+    #[cfg(feature = "yaml")]
+    pub fn parse(
+        frontmatter: &Frontmatter,
+        collection: &Collection,
+        id: &str,
+        offset_minutes: i32,
+    ) -> Result<Self, serde_yaml::Error> {
+        let mut yaml: serde_yaml::Value = frontmatter.deserialize()?;
+        // `Date`'s `Deserialize` cannot know the site timezone, so dates are stored as `YYYY-MM-DD` first
+        if let Some(mapping) = yaml.as_mapping_mut() {
+            for (key, value) in mapping.iter_mut() {
+                let is_date = match key.as_str() {
+                    Some("date" | "publish_date") => true,
+                    Some(name) => collection
+                        .field(name)
+                        .is_some_and(|field| field.kind == FieldKind::Date),
+                    None => false,
+                };
+                let date = value
+                    .as_str()
+                    .and_then(|text| Date::parse(text, offset_minutes));
+                if let Some(date) = date.filter(|_| is_date) {
+                    *value = serde_yaml::Value::String(date.to_string());
+                }
+            }
+        }
+        let mut meta: Self = serde_yaml::from_value(yaml)?;
+        meta.normalize(collection, id);
+        Ok(meta)
+    }
+
+    /// Brings freshly deserialized frontmatter into its indexed form: the id is
+    /// the folder name, a local `image_url` becomes a site path and fields the
+    /// collection does not declare are dropped.
+    pub fn normalize(&mut self, collection: &Collection, id: &str) {
+        self.id = id.to_string();
+        self.image_url = resolve_image_url(&self.image_url, collection.name, id);
+        self.fields
+            .retain(|name, _| collection.field(name).is_some());
+    }
+
+    /// A text field's value, if set.
+    pub fn text(&self, field: &str) -> Option<&str> {
+        self.fields.get(field)?.as_str()
+    }
+}
+
+#[cfg(all(test, feature = "yaml"))]
+mod tests {
+    use super::*;
+    use crate::frontmatter::split_frontmatter;
+
+    const TALKS: Collection = Collection {
+        name: "talks",
+        singular: "talk",
+        route: "talks",
+        label: "Talks",
+        title: "Talks",
+        subtitle: "",
+        item: "Talk",
+        search_placeholder: "",
+        fields: &[
+            Field {
+                name: "held_on",
+                kind: FieldKind::Date,
+                required: true,
+                label: Some("Held on"),
+            },
+            Field {
+                name: "slides",
+                kind: FieldKind::Link { text_field: None },
+                required: false,
+                label: Some("Slides"),
+            },
+        ],
+        feed: false,
+        share: false,
+        call_to_action: false,
+    };
+
+    fn parse(content: &str) -> EntryMeta {
+        let frontmatter = split_frontmatter(content).unwrap();
+        EntryMeta::parse(&frontmatter, &TALKS, "rust-meetup", 0).unwrap()
+    }
+
+    #[test]
+    fn normalizes_an_entry() {
+        let meta = parse(
+            "---\nid: ignored\ntitle: Rust Meetup\ndate: Jan 5, 2026\nauthor: Me\n\
+             description: A talk\nimage_url: ./cover.png\ntags: [Rust]\n\
+             held_on: 2026/01/07\nslides: https://example.com/slides\nvenue: Seoul\n---\nBody",
+        );
+        assert_eq!(meta.id, "rust-meetup");
+        assert_eq!(meta.date, Date::new(2026, 1, 5).unwrap());
+        assert_eq!(meta.image_url, "content/talks/rust-meetup/cover.png");
+        assert_eq!(meta.text("held_on"), Some("2026-01-07"));
+        assert_eq!(meta.text("slides"), Some("https://example.com/slides"));
+        assert!(!meta.fields.contains_key("venue"));
+        assert_eq!(meta.status, PublishStatus::Published);
+        assert_eq!(meta.publish_date(), meta.date);
+    }
+
+    #[test]
+    fn keeps_remote_images_and_reads_status() {
+        let meta = parse(
+            "---\ntitle: T\ndate: 2026-01-05\nauthor: Me\ndescription: D\n\
+             image_url: https://example.com/a.png\ntags: []\nstatus: scheduled\n\
+             publish_date: 2026-02-01\nseries: { id: intro, part: 2 }\n---\n",
+        );
+        assert_eq!(meta.image_url, "https://example.com/a.png");
+        assert_eq!(meta.status, PublishStatus::Scheduled);
+        assert_eq!(meta.publish_date(), Date::new(2026, 2, 1).unwrap());
+        assert_eq!(meta.series.map(|series| series.part), Some(2));
+    }
+
+    #[test]
+    fn dates_times_in_the_site_timezone() {
+        let frontmatter = split_frontmatter(
+            "---\ntitle: T\ndate: 2026-01-05T20:00:00Z\nauthor: Me\ndescription: D\n\
+             image_url: a.png\ntags: []\nheld_on: 2026-01-07T23:30:00-05:00\n---\n",
+        )
+        .unwrap();
+        let meta = EntryMeta::parse(&frontmatter, &TALKS, "id", 540).unwrap();
+        assert_eq!(meta.date, Date::new(2026, 1, 6).unwrap());
+        assert_eq!(meta.text("held_on"), Some("2026-01-08"));
+    }
+
+    #[test]
+    fn reports_missing_fields() {
+        let frontmatter = split_frontmatter("---\ntitle: T\n---\n").unwrap();
+        assert!(EntryMeta::parse(&frontmatter, &TALKS, "id", 0).is_err());
+    }
+
+    #[test]
+    fn index_round_trips_through_json() {
+        let meta = parse(
+            "---\ntitle: T\ndate: 2026-01-05\nauthor: Me\ndescription: D\n\
+             image_url: cover.png\ntags: [a]\nheld_on: 7 January 2026\n---\n",
+        );
+        let json = serde_json::to_string(&meta).unwrap();
+        assert!(json.contains("\"held_on\":\"2026-01-07\""));
+        assert_eq!(serde_json::from_str::<EntryMeta>(&json).unwrap(), meta);
+    }
+
+    #[test]
+    fn status_visibility() {
+        assert!(PublishStatus::Published.is_listed());
+        assert!(!PublishStatus::Unlisted.is_listed());
+        assert!(PublishStatus::Draft.is_draft());
+        assert!(PublishStatus::Scheduled.is_draft());
+        assert!(!PublishStatus::Unlisted.is_draft());
+    }
+}
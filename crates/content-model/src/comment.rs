@@ -0,0 +1,216 @@
+//! Comments on entries, as comment backends store and return them.
+//!
+//! A backend hands out a flat list in the order the comments were written;
+//! replies name the comment they answer with `parent`, and [`thread`] turns
+//! the list into the tree the page shows.
+
+use crate::date::Date;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Name of the file in an entry folder holding its static comments.
+pub const COMMENTS_FILE: &str = "comments.json";
+
+/// Site-relative path of the comment counts the build script writes for the
+/// static backend, keyed by collection name and then entry id.
+pub const COUNTS_PATH: &str = "content/comment_counts.json";
+
+/// Site-relative path of an entry's static comments with their bodies rendered,
+/// which the build script writes from the entry's `comments.json`.
+pub fn rendered_comments_file(collection: &str, id: &str) -> String {
+    format!("content/comments/{}/{}.json", collection, id)
+}
+
+/// Number of comments, replies included, per entry id.
+pub type CommentCounts = HashMap<String, usize>;
+
+/// A stored comment.
+///
+/// ```json
+/// { "id": "2", "parent": "1", "author": "Robert Fox", "date": "2026-01-14", "body": "Thanks!" }
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CommentRecord {
+    pub id: String,
+    /// Id of the comment this one replies to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    pub author: String,
+    pub date: Date,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
+    /// Markdown, sanitized with `COMMENT_POLICY` when displayed.
+    pub body: String,
+    /// `body` rendered to HTML, which the build script adds to static comments
+    /// and APIs may send. It is sanitized again when displayed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub html: Option<String>,
+}
+
+/// A comment a visitor submits.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NewComment {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    pub author: String,
+    pub body: String,
+}
+
+impl NewComment {
+    /// What keeps the comment from being posted, if anything.
+    pub fn problem(&self) -> Option<&'static str> {
+        if self.author.trim().is_empty() {
+            Some("Please enter your name.")
+        } else if self.body.trim().is_empty() {
+            Some("Please write a comment.")
+        } else {
+            None
+        }
+    }
+}
+
+/// A comment with its replies, oldest first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Thread {
+    pub comment: CommentRecord,
+    pub replies: Vec<Thread>,
+}
+
+/// Nests replies under the comments they answer, keeping the list order.
+/// A reply whose parent is unknown or comes after it is shown at the top
+/// level rather than lost.
+pub fn thread(comments: Vec<CommentRecord>) -> Vec<Thread> {
+    let mut seen = HashSet::new();
+    let mut roots = Vec::new();
+    let mut children: HashMap<String, Vec<CommentRecord>> = HashMap::new();
+    for comment in comments {
+        match &comment.parent {
+            Some(parent) if seen.contains(parent) => {
+                seen.insert(comment.id.clone());
+                children.entry(parent.clone()).or_default().push(comment);
+            }
+            _ => {
+                seen.insert(comment.id.clone());
+                roots.push(comment);
+            }
+        }
+    }
+    roots
+        .into_iter()
+        .map(|comment| attach(comment, &mut children))
+        .collect()
+}
+
+fn attach(comment: CommentRecord, children: &mut HashMap<String, Vec<CommentRecord>>) -> Thread {
+    let replies = children.remove(&comment.id).unwrap_or_default();
+    Thread {
+        comment,
+        replies: replies
+            .into_iter()
+            .map(|reply| attach(reply, children))
+            .collect(),
+    }
+}
+
+/// Problems with a stored comment list, such as a `comments.json` file.
+pub fn check(comments: &[CommentRecord]) -> Vec<String> {
+    let mut problems = Vec::new();
+    let mut seen = HashSet::new();
+    for comment in comments {
+        if !seen.insert(comment.id.as_str()) {
+            problems.push(format!("duplicate comment id `{}`", comment.id));
+        }
+        if let Some(parent) = &comment.parent {
+            if !seen.contains(parent.as_str()) || parent == &comment.id {
+                problems.push(format!(
+                    "comment `{}` replies to `{}`, which does not come before it",
+                    comment.id, parent
+                ));
+            }
+        }
+        if comment.author.trim().is_empty() {
+            problems.push(format!("comment `{}` has no author", comment.id));
+        }
+        if comment.body.trim().is_empty() {
+            problems.push(format!("comment `{}` has an empty body", comment.id));
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comment(id: &str, parent: Option<&str>) -> CommentRecord {
+        CommentRecord {
+            id: id.to_string(),
+            parent: parent.map(str::to_string),
+            author: "Jane".to_string(),
+            date: Date::new(2026, 1, 13).unwrap(),
+            avatar_url: None,
+            body: format!("comment {}", id),
+            html: None,
+        }
+    }
+
+    fn ids(threads: &[Thread]) -> Vec<String> {
+        threads
+            .iter()
+            .map(|thread| {
+                let replies = ids(&thread.replies);
+                if replies.is_empty() {
+                    thread.comment.id.clone()
+                } else {
+                    format!("{}({})", thread.comment.id, replies.join(" "))
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn nests_replies_in_order() {
+        let threads = thread(vec![
+            comment("1", None),
+            comment("2", None),
+            comment("3", Some("1")),
+            comment("4", Some("3")),
+            comment("5", Some("1")),
+        ]);
+        assert_eq!(ids(&threads), ["1(3(4) 5)", "2"]);
+    }
+
+    #[test]
+    fn keeps_replies_to_unknown_or_later_comments() {
+        let comments = vec![
+            comment("1", Some("missing")),
+            comment("2", Some("3")),
+            comment("3", None),
+        ];
+        assert_eq!(ids(&thread(comments.clone())), ["1", "2", "3"]);
+        assert_eq!(check(&comments).len(), 2);
+    }
+
+    #[test]
+    fn reports_duplicate_ids_and_empty_comments() {
+        let mut empty = comment("2", None);
+        empty.body = " ".to_string();
+        let problems = check(&[comment("1", None), comment("1", None), empty]);
+        assert_eq!(
+            problems,
+            ["duplicate comment id `1`", "comment `2` has an empty body"]
+        );
+    }
+
+    #[test]
+    fn parses_stored_comments() {
+        let comments: Vec<CommentRecord> = serde_json::from_str(
+            r#"[{ "id": "1", "author": "Jane", "date": "2026-01-13", "body": "Hi" },
+                { "id": "2", "parent": "1", "author": "Rob", "date": "2026-01-14", "body": "Hey" }]"#,
+        )
+        .unwrap();
+        assert_eq!(comments[1].parent.as_deref(), Some("1"));
+        assert_eq!(comments[0].date, Date::new(2026, 1, 13).unwrap());
+        assert!(check(&comments).is_empty());
+    }
+}
@@ -0,0 +1,420 @@
+//! Calendar dates for frontmatter, indexes and display.
+//!
+//! Frontmatter may write dates in several formats; they are parsed once at
+//! index time and stored as `YYYY-MM-DD`, so the runtime always sorts and
+//! formats real dates instead of strings.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+const MONTHS_EN: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// A day in the proleptic Gregorian calendar. Orders chronologically.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl Date {
+    /// Returns `None` for days that do not exist, such as February 30th.
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        let days_in_month = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+            2 => 28,
+            _ => return None,
+        };
+        (1..=days_in_month)
+            .contains(&day)
+            .then_some(Self { year, month, day })
+    }
+
+    /// Parses the date formats accepted in frontmatter:
+    ///
+    /// - `2026-01-05`, `2026-1-5`, `2026/01/05`, `2026.01.05`
+    /// - `2026-01-05T23:30:00+09:00` (converted to the timezone `offset_minutes`
+    ///   east of UTC when an offset is given)
+    /// - `Jan 5, 2026`, `January 5 2026`, `5 Jan 2026`
+    /// - `2026년 1월 5일`
+    pub fn parse(text: &str, offset_minutes: i32) -> Option<Self> {
+        let text = text.trim();
+        if text.contains('년') {
+            let numbers: Vec<&str> = text
+                .split(|c: char| !c.is_ascii_digit())
+                .filter(|part| !part.is_empty())
+                .collect();
+            return match numbers[..] {
+                [year, month, day] => {
+                    Self::new(year.parse().ok()?, month.parse().ok()?, day.parse().ok()?)
+                }
+                _ => None,
+            };
+        }
+        if text.starts_with(|c: char| c.is_ascii_digit()) && text.len() >= 8 {
+            if let Some(date) = Self::parse_numeric(text, offset_minutes) {
+                return Some(date);
+            }
+        }
+        Self::parse_month_name(text)
+    }
+
+    /// `YYYY-MM-DD` with `-`, `/` or `.` separators and an optional time and offset.
+    fn parse_numeric(text: &str, offset_minutes: i32) -> Option<Self> {
+        let (date, time) = match text.find(['T', ' ']) {
+            Some(idx) => (&text[..idx], Some(text[idx + 1..].trim())),
+            None => (text, None),
+        };
+        let separator = date.chars().find(|c| matches!(c, '-' | '/' | '.'))?;
+        let parts: Vec<&str> = date.split(separator).collect();
+        let [year, month, day] = parts[..] else {
+            return None;
+        };
+        if year.len() != 4 || month.is_empty() || month.len() > 2 || day.is_empty() || day.len() > 2
+        {
+            return None;
+        }
+        let date = Self::new(year.parse().ok()?, month.parse().ok()?, day.parse().ok()?)?;
+        match time {
+            Some(time) => date.at_time(time, offset_minutes),
+            None => Some(date),
+        }
+    }
+
+    /// Applies `HH:MM[:SS][Z|±HH:MM]`; with an offset the instant is moved to `site_offset`.
+    fn at_time(self, time: &str, site_offset: i32) -> Option<Self> {
+        let (clock, offset) = match time.find(['Z', 'z', '+', '-']) {
+            Some(idx) => (&time[..idx], Some(&time[idx..])),
+            None => (time, None),
+        };
+        let fields: Vec<i64> = clock
+            .split(':')
+            .map(|field| field.split('.').next().unwrap_or_default().parse().ok())
+            .collect::<Option<_>>()?;
+        let (hours, minutes, seconds) = match fields[..] {
+            [h, m] => (h, m, 0),
+            [h, m, s] => (h, m, s),
+            _ => return None,
+        };
+        if hours > 23 || minutes > 59 || seconds > 60 {
+            return None;
+        }
+        let Some(offset) = offset else {
+            return Some(self);
+        };
+        let time_offset = match offset {
+            "Z" | "z" => 0,
+            _ => {
+                let sign = if offset.starts_with('-') { -1 } else { 1 };
+                let digits: String = offset[1..].chars().filter(char::is_ascii_digit).collect();
+                if digits.len() != 4 {
+                    return None;
+                }
+                sign * (digits[..2].parse::<i64>().ok()? * 60 + digits[2..].parse::<i64>().ok()?)
+            }
+        };
+        let utc = self.days_since_epoch() * 86_400 + hours * 3_600 + minutes * 60 + seconds
+            - time_offset * 60;
+        Some(Self::from_unix(utc, site_offset))
+    }
+
+    /// `Jan 5, 2026` or `5 January 2026`; month names may be abbreviated to three letters.
+    fn parse_month_name(text: &str) -> Option<Self> {
+        let words: Vec<&str> = text
+            .split([' ', ','])
+            .filter(|word| !word.is_empty())
+            .collect();
+        let (month_word, day, year) = match words[..] {
+            [first, second, year] if first.starts_with(|c: char| c.is_alphabetic()) => {
+                (first, second, year)
+            }
+            [day, month, year] => (month, day, year),
+            _ => return None,
+        };
+        let month_word = month_word.trim_end_matches('.').to_lowercase();
+        let month = MONTHS_EN.iter().position(|name| {
+            let name = name.to_lowercase();
+            month_word.len() >= 3 && name.starts_with(&month_word)
+        })?;
+        let day = day.trim_end_matches(|c: char| c.is_alphabetic());
+        Self::new(year.parse().ok()?, month as u32 + 1, day.parse().ok()?)
+    }
+
+    /// The calendar day at `secs` since the Unix epoch, at a UTC offset in minutes.
+    pub fn from_unix(secs: i64, offset_minutes: i32) -> Self {
+        let days = (secs + offset_minutes as i64 * 60).div_euclid(86_400);
+        // Civil-from-days conversion (proleptic Gregorian calendar)
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+        Self {
+            year: year as i32,
+            month: month as u32,
+            day: day as u32,
+        }
+    }
+
+    /// Days since 1970-01-01.
+    pub fn days_since_epoch(&self) -> i64 {
+        let (month, day) = (self.month as i64, self.day as i64);
+        let y = if month <= 2 {
+            self.year as i64 - 1
+        } else {
+            self.year as i64
+        };
+        let era = y.div_euclid(400);
+        let yoe = y.rem_euclid(400);
+        let mp = (month + 9) % 12;
+        let doy = (153 * mp + 2) / 5 + day - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+
+    /// Day of the week, `0` for Monday through `6` for Sunday.
+    pub fn weekday(&self) -> usize {
+        // 1970-01-01 was a Thursday
+        (self.days_since_epoch() + 3).rem_euclid(7) as usize
+    }
+
+    /// Formats with `%Y`, `%m`, `%d`, `%-m`, `%-d`, `%B` (month name) and `%b`
+    /// (short month name); month names follow `locale` (`en` or `ko`).
+    pub fn format(&self, pattern: &str, locale: &str) -> String {
+        let month_name = match locale {
+            "ko" => format!("{}월", self.month),
+            _ => MONTHS_EN[self.month as usize - 1].to_string(),
+        };
+        let mut out = String::new();
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('Y') => out.push_str(&self.year.to_string()),
+                Some('m') => out.push_str(&format!("{:02}", self.month)),
+                Some('d') => out.push_str(&format!("{:02}", self.day)),
+                Some('B') => out.push_str(&month_name),
+                Some('b') if locale == "ko" => out.push_str(&month_name),
+                Some('b') => out.push_str(&month_name[..3]),
+                Some('-') => match chars.next() {
+                    Some('m') => out.push_str(&self.month.to_string()),
+                    Some('d') => out.push_str(&self.day.to_string()),
+                    other => {
+                        out.push_str("%-");
+                        out.extend(other);
+                    }
+                },
+                Some('%') => out.push('%'),
+                other => {
+                    out.push('%');
+                    out.extend(other);
+                }
+            }
+        }
+        out
+    }
+
+    /// How long ago the date was, seen from `today`: "yesterday", "3 days ago", "2 months ago".
+    pub fn relative(&self, today: Date, locale: &str) -> String {
+        let days = today.days_since_epoch() - self.days_since_epoch();
+        let (amount, unit) = match days.abs() {
+            0..=6 => (days.abs(), 0),
+            7..=29 => (days.abs() / 7, 1),
+            30..=364 => (days.abs() / 30, 2),
+            _ => (days.abs() / 365, 3),
+        };
+        match locale {
+            "ko" => match days {
+                0 => "오늘".to_string(),
+                1 => "어제".to_string(),
+                -1 => "내일".to_string(),
+                _ => {
+                    let unit = ["일", "주", "개월", "년"][unit];
+                    let direction = if days > 0 { "전" } else { "후" };
+                    format!("{}{} {}", amount, unit, direction)
+                }
+            },
+            _ => match days {
+                0 => "today".to_string(),
+                1 => "yesterday".to_string(),
+                -1 => "tomorrow".to_string(),
+                _ => {
+                    let unit = ["day", "week", "month", "year"][unit];
+                    let plural = if amount == 1 { "" } else { "s" };
+                    if days > 0 {
+                        format!("{} {}{} ago", amount, unit, plural)
+                    } else {
+                        format!("in {} {}{}", amount, unit, plural)
+                    }
+                }
+            },
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    /// `YYYY-MM-DD`, as stored in the generated indexes.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl Serialize for Date {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Date {
+    /// Any format [`Date::parse`] accepts, with times dated in UTC; indexes
+    /// store `YYYY-MM-DD`, which needs no timezone.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        Date::parse(&text, 0)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid date `{}`", text)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn rejects_days_that_do_not_exist() {
+        assert!(Date::new(2026, 2, 30).is_none());
+        assert!(Date::new(2026, 13, 1).is_none());
+        assert!(Date::new(2026, 4, 31).is_none());
+        assert!(Date::new(2024, 2, 29).is_some());
+        assert!(Date::new(2100, 2, 29).is_none());
+        assert!(Date::new(2000, 2, 29).is_some());
+    }
+
+    #[test]
+    fn parses_numeric_dates() {
+        for text in [
+            "2026-01-05",
+            "2026-1-5",
+            "2026/01/05",
+            "2026.01.05",
+            " 2026-01-05 ",
+        ] {
+            assert_eq!(Date::parse(text, 0), Some(date(2026, 1, 5)), "{}", text);
+        }
+        assert_eq!(Date::parse("2026-02-30", 0), None);
+        assert_eq!(Date::parse("26-01-05", 0), None);
+    }
+
+    #[test]
+    fn parses_month_names_and_korean() {
+        for text in [
+            "Jan 5, 2026",
+            "January 5 2026",
+            "5 Jan 2026",
+            "5 January 2026",
+            "Jan. 5th, 2026",
+            "2026년 1월 5일",
+        ] {
+            assert_eq!(Date::parse(text, 0), Some(date(2026, 1, 5)), "{}", text);
+        }
+        assert_eq!(Date::parse("Ja 5, 2026", 0), None);
+        assert_eq!(Date::parse("2026년 1월", 0), None);
+    }
+
+    #[test]
+    fn converts_times_to_the_site_timezone() {
+        let text = "2026-01-05T23:30:00+09:00";
+        assert_eq!(Date::parse(text, 0), Some(date(2026, 1, 5)));
+        assert_eq!(Date::parse(text, 540), Some(date(2026, 1, 5)));
+        assert_eq!(
+            Date::parse("2026-01-05T20:00:00Z", 540),
+            Some(date(2026, 1, 6))
+        );
+        assert_eq!(
+            Date::parse("2026-01-05 01:00-05:00", 0),
+            Some(date(2026, 1, 5))
+        );
+        assert_eq!(
+            Date::parse("2026-01-05T01:00:00+09:00", 0),
+            Some(date(2026, 1, 4))
+        );
+        // Without an offset the time is already local
+        assert_eq!(Date::parse("2026-01-05T23:30", 540), Some(date(2026, 1, 5)));
+        assert_eq!(Date::parse("2026-01-05T25:00", 0), None);
+    }
+
+    #[test]
+    fn unix_days_round_trip() {
+        assert_eq!(Date::from_unix(0, 0), date(1970, 1, 1));
+        assert_eq!(Date::from_unix(-1, 0), date(1969, 12, 31));
+        assert_eq!(Date::from_unix(0, 540), date(1970, 1, 1));
+        assert_eq!(Date::from_unix(86_400 - 1, 60), date(1970, 1, 2));
+        for day in [date(2000, 2, 29), date(2026, 12, 31), date(1900, 3, 1)] {
+            assert_eq!(Date::from_unix(day.days_since_epoch() * 86_400, 0), day);
+        }
+        assert_eq!(date(1970, 1, 1).weekday(), 3);
+        assert_eq!(date(2026, 1, 5).weekday(), 0);
+    }
+
+    #[test]
+    fn formats_with_patterns_and_locales() {
+        let day = date(2026, 1, 5);
+        assert_eq!(day.format("%B %-d, %Y", "en"), "January 5, 2026");
+        assert_eq!(day.format("%b %d %Y", "en"), "Jan 05 2026");
+        assert_eq!(day.format("%Y-%m-%d", "en"), "2026-01-05");
+        assert_eq!(day.format("%Y년 %-m월 %-d일", "ko"), "2026년 1월 5일");
+        assert_eq!(day.format("%B", "ko"), "1월");
+        assert_eq!(day.format("100%% %q", "en"), "100% %q");
+        assert_eq!(day.to_string(), "2026-01-05");
+    }
+
+    #[test]
+    fn describes_relative_dates() {
+        let today = date(2026, 3, 10);
+        assert_eq!(date(2026, 3, 10).relative(today, "en"), "today");
+        assert_eq!(date(2026, 3, 9).relative(today, "en"), "yesterday");
+        assert_eq!(date(2026, 3, 11).relative(today, "en"), "tomorrow");
+        assert_eq!(date(2026, 3, 7).relative(today, "en"), "3 days ago");
+        assert_eq!(date(2026, 3, 3).relative(today, "en"), "1 week ago");
+        assert_eq!(date(2026, 1, 1).relative(today, "en"), "2 months ago");
+        assert_eq!(date(2024, 3, 1).relative(today, "en"), "2 years ago");
+        assert_eq!(date(2026, 3, 20).relative(today, "en"), "in 1 week");
+        assert_eq!(date(2026, 3, 7).relative(today, "ko"), "3일 전");
+        assert_eq!(date(2026, 3, 9).relative(today, "ko"), "어제");
+        assert_eq!(date(2026, 3, 20).relative(today, "ko"), "1주 후");
+    }
+
+    #[test]
+    fn serializes_as_iso_dates() {
+        let day = date(2026, 1, 5);
+        assert_eq!(serde_json::to_string(&day).unwrap(), "\"2026-01-05\"");
+        let parsed: Date = serde_json::from_str("\"Jan 5, 2026\"").unwrap();
+        assert_eq!(parsed, day);
+        assert!(serde_json::from_str::<Date>("\"someday\"").is_err());
+    }
+}
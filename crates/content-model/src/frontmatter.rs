@@ -0,0 +1,111 @@
+//! The `---` delimited YAML block at the top of every `index.md`.
+
+/// The YAML block of an `index.md`, with the file line its first line maps to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Frontmatter<'a> {
+    pub yaml: &'a str,
+    pub base_line: usize,
+    /// Markdown following the closing `---` line.
+    pub body: &'a str,
+}
+
+impl Frontmatter<'_> {
+    /// File line of a top-level `key:` entry, if present.
+    pub fn key_line(&self, key: &str) -> Option<usize> {
+        let prefix = format!("{}:", key);
+        self.yaml
+            .lines()
+            .position(|line| line.starts_with(&prefix))
+            .map(|idx| self.base_line + idx)
+    }
+
+    /// Maps a 1-based line inside the YAML block to a file line.
+    pub fn file_line(&self, yaml_line: usize) -> usize {
+        self.base_line + yaml_line.saturating_sub(1)
+    }
+
+    /// Deserializes the YAML block.
+    #[cfg(feature = "yaml")]
+    pub fn deserialize<T: serde::de::DeserializeOwned>(&self) -> Result<T, serde_yaml::Error> {
+        serde_yaml::from_str(self.yaml)
+    }
+}
+
+/// Locates the `---` delimited frontmatter at the top of a markdown file.
+/// Leading blank lines are allowed; the block ends at the first line starting with `---`.
+pub fn split_frontmatter(content: &str) -> Option<Frontmatter<'_>> {
+    let trimmed = content.trim_start();
+    if !trimmed.starts_with("---") {
+        return None;
+    }
+    let offset = content.len() - trimmed.len();
+    let yaml_start = offset + 3;
+    let yaml_end = yaml_start + content[yaml_start..].find("\n---")? + 1;
+    let body = content[yaml_end + 3..]
+        .split_once('\n')
+        .map_or("", |(_, body)| body);
+    Some(Frontmatter {
+        yaml: &content[yaml_start..yaml_end],
+        base_line: content[..yaml_start].matches('\n').count() + 1,
+        body,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_yaml_from_body() {
+        let fm = split_frontmatter("---\ntitle: Hello\ntags: [a]\n---\n# Heading\n").unwrap();
+        assert_eq!(fm.yaml, "\ntitle: Hello\ntags: [a]\n");
+        assert_eq!(fm.body, "# Heading\n");
+        assert_eq!(fm.base_line, 1);
+        assert_eq!(fm.key_line("title"), Some(2));
+        assert_eq!(fm.key_line("tags"), Some(3));
+        assert_eq!(fm.key_line("author"), None);
+    }
+
+    #[test]
+    fn allows_leading_blank_lines() {
+        let fm = split_frontmatter("\n\n---\ntitle: Hello\n---\nBody").unwrap();
+        assert_eq!(fm.base_line, 3);
+        assert_eq!(fm.key_line("title"), Some(4));
+        assert_eq!(fm.file_line(2), 4);
+        assert_eq!(fm.body, "Body");
+    }
+
+    #[test]
+    fn handles_crlf_line_endings() {
+        let fm = split_frontmatter("---\r\ntitle: Hello\r\n---\r\nBody\r\n").unwrap();
+        assert_eq!(fm.yaml.trim(), "title: Hello");
+        assert_eq!(fm.body, "Body\r\n");
+    }
+
+    #[test]
+    fn dashes_inside_values_do_not_close_the_block() {
+        let fm = split_frontmatter("---\ntitle: Before --- after\n---\nBody --- text").unwrap();
+        assert_eq!(fm.yaml, "\ntitle: Before --- after\n");
+        assert_eq!(fm.body, "Body --- text");
+    }
+
+    #[test]
+    fn rejects_missing_or_unclosed_blocks() {
+        assert_eq!(split_frontmatter("# Just markdown\n---\n"), None);
+        assert_eq!(split_frontmatter("---\ntitle: Hello\n"), None);
+    }
+
+    #[test]
+    fn empty_body_after_closing_line() {
+        let fm = split_frontmatter("---\ntitle: Hello\n---").unwrap();
+        assert_eq!(fm.body, "");
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn deserializes_the_block() {
+        let fm = split_frontmatter("---\ntitle: Hello\n---\n").unwrap();
+        let value: std::collections::BTreeMap<String, String> = fm.deserialize().unwrap();
+        assert_eq!(value["title"], "Hello");
+    }
+}
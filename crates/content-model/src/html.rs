@@ -0,0 +1,480 @@
+//! HTML fragments as a tree of [`Node`]s: the shape rendered entries are
+//! stored in, a forgiving parser for raw HTML and a serializer back to markup.
+//!
+//! The parser is not a full HTML5 parser. It reads the markup markdown writers
+//! and authors produce, closes unbalanced tags the way browsers do for inline
+//! content, and keeps anything that does not look like a tag as text, so a
+//! tree it builds never smuggles markup through a [`Node::Text`].
+
+use crate::shortcode::{marker, parse_marker};
+use serde::{Deserialize, Serialize};
+
+/// A node of a rendered document, displayed by the app as Dioxus elements.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Node {
+    Element {
+        tag: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        attrs: Vec<(String, String)>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        children: Vec<Node>,
+    },
+    Text(String),
+    /// Source of a shortcode, mounted as a component (see `shortcode.rs`).
+    Shortcode(String),
+}
+
+impl Node {
+    /// The value of an element's attribute.
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        match self {
+            Node::Element { attrs, .. } => attrs
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str()),
+            _ => None,
+        }
+    }
+}
+
+/// Elements that never have children or a closing tag.
+pub const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Parses an HTML fragment. Shortcode markers become [`Node::Shortcode`]s and
+/// other comments are dropped.
+pub fn parse_fragment(html: &str) -> Vec<Node> {
+    let mut tree = TreeBuilder::default();
+    tree.html(html);
+    tree.finish()
+}
+
+/// Serializes nodes back to HTML, escaping all text and attribute values.
+pub fn to_html(nodes: &[Node]) -> String {
+    let mut html = String::new();
+    for node in nodes {
+        write_node(node, &mut html);
+    }
+    html
+}
+
+fn write_node(node: &Node, html: &mut String) {
+    match node {
+        Node::Element {
+            tag,
+            attrs,
+            children,
+        } => {
+            html.push('<');
+            html.push_str(tag);
+            for (name, value) in attrs {
+                html.push_str(&format!(r#" {}="{}""#, name, escape(value)));
+            }
+            html.push('>');
+            if !VOID_ELEMENTS.contains(&tag.as_str()) {
+                for child in children {
+                    write_node(child, html);
+                }
+                html.push_str(&format!("</{}>", tag));
+            }
+        }
+        Node::Text(text) => html.push_str(&escape(text)),
+        Node::Shortcode(source) => html.push_str(&marker(source)),
+    }
+}
+
+/// Escapes text for use in HTML or XML content or a double-quoted attribute value.
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// An element whose end has not been reached yet.
+struct OpenElement {
+    tag: String,
+    attrs: Vec<(String, String)>,
+    children: Vec<Node>,
+}
+
+/// Assembles a tree from open and close calls, closing elements that were
+/// left open the way browsers do.
+#[derive(Default)]
+pub struct TreeBuilder {
+    /// Open elements, innermost last, each with the children collected so far.
+    open: Vec<OpenElement>,
+    root: Vec<Node>,
+}
+
+impl TreeBuilder {
+    /// Feeds raw HTML through the same open/close logic as [`Self::open`] and [`Self::close`].
+    pub fn html(&mut self, html: &str) {
+        for token in tokenize(html) {
+            match token {
+                Token::Open(tag, attrs) if VOID_ELEMENTS.contains(&tag.as_str()) => {
+                    self.void(&tag, attrs)
+                }
+                Token::Open(tag, attrs) => self.open(&tag, attrs),
+                Token::SelfClosing(tag, attrs) => self.void(&tag, attrs),
+                Token::Close(tag) => self.close(&tag),
+                Token::Text(text) => self.text(&text),
+                Token::Comment(comment) => {
+                    if let Some(source) = parse_marker(&comment) {
+                        self.push(Node::Shortcode(source));
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn push(&mut self, node: Node) {
+        match self.open.last_mut() {
+            Some(element) => element.children.push(node),
+            None => self.root.push(node),
+        }
+    }
+
+    /// Appends text, merging it with the text right before it.
+    pub fn text(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        let children = match self.open.last_mut() {
+            Some(element) => &mut element.children,
+            None => &mut self.root,
+        };
+        match children.last_mut() {
+            Some(Node::Text(last)) => last.push_str(text),
+            _ => children.push(Node::Text(text.to_string())),
+        }
+    }
+
+    pub fn open(&mut self, tag: &str, attrs: Vec<(String, String)>) {
+        self.open.push(OpenElement {
+            tag: tag.to_ascii_lowercase(),
+            attrs,
+            children: Vec::new(),
+        });
+    }
+
+    /// Adds an element without children.
+    pub fn void(&mut self, tag: &str, attrs: Vec<(String, String)>) {
+        self.push(Node::Element {
+            tag: tag.to_ascii_lowercase(),
+            attrs,
+            children: Vec::new(),
+        });
+    }
+
+    /// Closes the innermost open `tag` and everything opened inside it. A
+    /// closing tag with nothing to close is ignored, as browsers do.
+    pub fn close(&mut self, tag: &str) {
+        let Some(index) = self
+            .open
+            .iter()
+            .rposition(|element| element.tag.eq_ignore_ascii_case(tag))
+        else {
+            return;
+        };
+        while self.open.len() > index {
+            self.pop();
+        }
+    }
+
+    fn pop(&mut self) {
+        if let Some(element) = self.open.pop() {
+            self.push(Node::Element {
+                tag: element.tag,
+                attrs: element.attrs,
+                children: element.children,
+            });
+        }
+    }
+
+    /// Closes everything still open and returns the tree.
+    pub fn finish(mut self) -> Vec<Node> {
+        while !self.open.is_empty() {
+            self.pop();
+        }
+        // Whitespace between blocks carries no meaning outside an element
+        self.root
+            .retain(|node| !matches!(node, Node::Text(text) if text.trim().is_empty()));
+        self.root
+    }
+}
+
+enum Token {
+    Open(String, Vec<(String, String)>),
+    SelfClosing(String, Vec<(String, String)>),
+    Close(String),
+    Text(String),
+    Comment(String),
+}
+
+/// Elements whose content is text up to their closing tag, never markup.
+const RAW_TEXT_ELEMENTS: &[&str] = &[
+    "script", "style", "textarea", "title", "xmp", "iframe", "noembed", "noframes", "noscript",
+];
+
+/// Splits an HTML fragment into tags, text and comments. Anything that does
+/// not look like a tag is kept as text, and entities are decoded.
+fn tokenize(html: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            tokens.push(Token::Text(decode_entities(rest)));
+            break;
+        };
+        if start > 0 {
+            tokens.push(Token::Text(decode_entities(&rest[..start])));
+            rest = &rest[start..];
+        }
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            let end = comment.find("-->").unwrap_or(comment.len());
+            tokens.push(Token::Comment(comment[..end].to_string()));
+            rest = comment.get(end + 3..).unwrap_or_default();
+        } else if let Some((token, len)) = parse_tag(rest) {
+            let raw_text = match &token {
+                Token::Open(tag, _) if RAW_TEXT_ELEMENTS.contains(&tag.as_str()) => {
+                    Some(tag.clone())
+                }
+                _ => None,
+            };
+            tokens.push(token);
+            rest = &rest[len..];
+            if let Some(tag) = raw_text {
+                let close = format!("</{}", tag);
+                let end = rest.to_ascii_lowercase().find(&close).unwrap_or(rest.len());
+                if end > 0 {
+                    tokens.push(Token::Text(rest[..end].to_string()));
+                }
+                rest = &rest[end..];
+            }
+        } else {
+            tokens.push(Token::Text("<".to_string()));
+            rest = &rest[1..];
+        }
+    }
+    tokens
+}
+
+/// Reads the tag at the start of `html` (`<name attr="value">`, `</name>`,
+/// `<name/>` or `<!DOCTYPE ...>`), returning it and its length in bytes.
+fn parse_tag(html: &str) -> Option<(Token, usize)> {
+    let bytes = html.as_bytes();
+    let mut i = 1;
+    let closing = bytes.get(i) == Some(&b'/');
+    if closing {
+        i += 1;
+    }
+    if bytes.get(i) == Some(&b'!') {
+        // Declarations such as <!DOCTYPE html> are dropped
+        let end = html.find('>')?;
+        return Some((Token::Comment(String::new()), end + 1));
+    }
+    let name_start = i;
+    while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'-') {
+        i += 1;
+    }
+    if i == name_start || !bytes[name_start].is_ascii_alphabetic() {
+        return None;
+    }
+    let name = html[name_start..i].to_ascii_lowercase();
+    if closing {
+        let end = i + html[i..].find('>')?;
+        return Some((Token::Close(name), end + 1));
+    }
+
+    let mut attrs = Vec::new();
+    loop {
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        match bytes.get(i)? {
+            b'>' => return Some((Token::Open(name, attrs), i + 1)),
+            b'/' if bytes.get(i + 1) == Some(&b'>') => {
+                return Some((Token::SelfClosing(name, attrs), i + 2))
+            }
+            b'/' => {
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+        let attr_start = i;
+        while i < bytes.len()
+            && !bytes[i].is_ascii_whitespace()
+            && !matches!(bytes[i], b'=' | b'>' | b'/')
+        {
+            i += 1;
+        }
+        let attr_name = html[attr_start..i].to_ascii_lowercase();
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        let value = if bytes.get(i) == Some(&b'=') {
+            i += 1;
+            while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+            match bytes.get(i)? {
+                quote @ (b'"' | b'\'') => {
+                    let end = i + 1 + html[i + 1..].find(*quote as char)?;
+                    let value = &html[i + 1..end];
+                    i = end + 1;
+                    value
+                }
+                _ => {
+                    let start = i;
+                    while i < bytes.len() && !bytes[i].is_ascii_whitespace() && bytes[i] != b'>' {
+                        i += 1;
+                    }
+                    &html[start..i]
+                }
+            }
+        } else {
+            ""
+        };
+        if !attr_name.is_empty() {
+            attrs.push((attr_name, decode_entities(value)));
+        }
+    }
+}
+
+/// Decodes the character references HTML writers commonly emit. Numeric
+/// references are read with or without their `;`, as browsers read them.
+fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match decode_entity(&rest[1..]) {
+            Some((character, len)) => {
+                decoded.push(character);
+                rest = &rest[1 + len..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// The character referenced at the start of `text` (just past the `&`) and
+/// the length of the reference.
+fn decode_entity(text: &str) -> Option<(char, usize)> {
+    if let Some(number) = text.strip_prefix('#') {
+        let (digits, radix, prefix) = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => (hex, 16, 2),
+            None => (number, 10, 1),
+        };
+        let end = digits
+            .find(|c: char| !c.is_digit(radix))
+            .unwrap_or(digits.len());
+        if end == 0 {
+            return None;
+        }
+        let code = u32::from_str_radix(&digits[..end], radix).unwrap_or(u32::MAX);
+        let character = char::from_u32(code)
+            .filter(|c| *c != '\0')
+            .unwrap_or('\u{fffd}');
+        let semicolon = usize::from(digits[end..].starts_with(';'));
+        return Some((character, prefix + end + semicolon));
+    }
+    let end = text.find(';').filter(|end| *end <= 10)?;
+    let character = match &text[..end] {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        "colon" => ':',
+        "Tab" => '\t',
+        "NewLine" => '\n',
+        _ => return None,
+    };
+    Some((character, end + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn element(tag: &str, attrs: &[(&str, &str)], children: Vec<Node>) -> Node {
+        Node::Element {
+            tag: tag.to_string(),
+            attrs: attrs
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            children,
+        }
+    }
+
+    fn text(text: &str) -> Node {
+        Node::Text(text.to_string())
+    }
+
+    #[test]
+    fn parses_nested_markup() {
+        assert_eq!(
+            parse_fragment(r#"<p class="lead">Hi <b>there</b><br>&amp; bye</p>"#),
+            vec![element(
+                "p",
+                &[("class", "lead")],
+                vec![
+                    text("Hi "),
+                    element("b", &[], vec![text("there")]),
+                    element("br", &[], vec![]),
+                    text("& bye"),
+                ],
+            )]
+        );
+    }
+
+    #[test]
+    fn closes_unbalanced_tags() {
+        assert_eq!(
+            parse_fragment("<b><i>x</b>y</i><div>z"),
+            vec![
+                element("b", &[], vec![element("i", &[], vec![text("x")])]),
+                text("y"),
+                element("div", &[], vec![text("z")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn decodes_character_references() {
+        assert_eq!(
+            decode_entities("&lt;&#106;&#x61&#118;a&colon;&bogus;"),
+            "<java:&bogus;"
+        );
+        assert_eq!(decode_entities("&#0;&#x110000;"), "\u{fffd}\u{fffd}");
+    }
+
+    #[test]
+    fn round_trips_through_html() {
+        let html = r#"<p title="&quot;q&quot;">a &lt; b<img src="x.png" alt=""></p><!--{{< post id="a%2Db" >}}-->"#;
+        let nodes = parse_fragment(html);
+        assert_eq!(
+            nodes[1],
+            Node::Shortcode(r#"{{< post id="a-b" >}}"#.to_string())
+        );
+        assert_eq!(to_html(&nodes), html);
+    }
+}
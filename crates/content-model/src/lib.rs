@@ -0,0 +1,23 @@
+//! The content model of the blog: what an entry's frontmatter may contain,
+//! how it is parsed and where its files live on the site.
+//!
+//! Both the build script (which writes the indexes) and the app (which reads
+//! them, and renders `index.md` itself with `runtime-markdown`) depend on this
+//! crate, so an index and a detail page always agree about the same file.
+
+pub mod collection;
+pub mod comment;
+pub mod date;
+pub mod frontmatter;
+pub mod html;
+pub mod path;
+pub mod rendered;
+pub mod responsive;
+pub mod sanitize;
+pub mod search;
+pub mod series;
+pub mod shortcode;
+pub mod slug;
+pub mod taxonomy;
+pub mod tokenizer;
+pub mod wikilink;
@@ -0,0 +1,92 @@
+//! Where an entry's files are served from.
+//!
+//! Frontmatter images and markdown links resolve with the same rules
+//! everywhere: anything with a URL scheme (`https:`, `mailto:`, ...) is
+//! external, `/...` is relative to the site root, and everything else is a
+//! file in the entry's folder.
+
+/// Whether a link or image points outside the site, i.e. starts with a URL
+/// scheme such as `https:` or `mailto:`.
+pub fn is_external(url: &str) -> bool {
+    url.split_once(':').is_some_and(|(scheme, _)| {
+        scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    })
+}
+
+/// Whether a link or image names a file in the entry's own folder.
+pub fn is_entry_relative(url: &str) -> bool {
+    !is_external(url) && !url.starts_with('/')
+}
+
+/// Site-relative path of a file in an entry folder, e.g. `content/posts/my-post/cover.png`.
+pub fn entry_file(collection: &str, id: &str, file: &str) -> String {
+    format!(
+        "content/{}/{}/{}",
+        collection,
+        id,
+        file.trim_start_matches("./")
+    )
+}
+
+/// Resolves a frontmatter `image_url` to a site-relative path, leaving
+/// external and root-relative URLs untouched.
+pub fn resolve_image_url(url: &str, collection: &str, id: &str) -> String {
+    if is_entry_relative(url) {
+        entry_file(collection, id, url)
+    } else {
+        url.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_urls() {
+        assert!(is_external("https://example.com/a.png"));
+        assert!(is_external("http://example.com"));
+        assert!(is_external("mailto:me@example.com"));
+        assert!(is_external("tel:+15550100"));
+        assert!(is_external("ftp://example.com/file.txt"));
+        assert!(is_external("svn+ssh://example.com/repo"));
+        assert!(!is_external("https-notes.md"));
+        assert!(!is_external("http.png"));
+        assert!(!is_external("img/a:b.png"));
+        assert!(!is_external("#note:1"));
+        assert!(!is_external("/search?q=a:b"));
+        assert!(!is_entry_relative("mailto:me@example.com"));
+        assert!(!is_entry_relative("https://example.com/a.png"));
+        assert!(!is_entry_relative("/assets/logo.png"));
+        assert!(is_entry_relative("cover.png"));
+        assert!(is_entry_relative("./img/cover.png"));
+        assert!(is_entry_relative("#section"));
+    }
+
+    #[test]
+    fn resolves_files_in_the_entry_folder() {
+        assert_eq!(
+            entry_file("posts", "my-post", "./cover.png"),
+            "content/posts/my-post/cover.png"
+        );
+        assert_eq!(
+            resolve_image_url("img/cover.png", "projects", "tool"),
+            "content/projects/tool/img/cover.png"
+        );
+    }
+
+    #[test]
+    fn keeps_external_and_root_relative_images() {
+        assert_eq!(
+            resolve_image_url("https://cdn.example.com/a.png", "posts", "p"),
+            "https://cdn.example.com/a.png"
+        );
+        assert_eq!(
+            resolve_image_url("/assets/header.svg", "posts", "p"),
+            "/assets/header.svg"
+        );
+    }
+}
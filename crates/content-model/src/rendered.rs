@@ -0,0 +1,44 @@
+//! Shapes of the pre-rendered content written by the build script.
+
+use crate::html::Node;
+use crate::responsive::ResponsiveImage;
+use serde::{Deserialize, Serialize};
+
+/// A heading in a rendered document, used for the table of contents.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TocEntry {
+    pub level: u8,
+    pub text: String,
+    /// `id` of the heading in the rendered HTML, unique within the document.
+    pub id: String,
+}
+
+/// An entry of any collection with its body already rendered to a document tree.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RenderedContent<M> {
+    pub meta: M,
+    pub body: Vec<Node>,
+    pub toc: Vec<TocEntry>,
+    pub word_count: usize,
+    /// Entries recommended after this one, best first.
+    #[serde(default)]
+    pub related: Vec<RelatedEntry>,
+    /// Entries whose body links to this one with a `[[wiki link]]`, newest first.
+    #[serde(default)]
+    pub backlinks: Vec<RelatedEntry>,
+}
+
+/// A recommended entry, with what its card needs to render.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RelatedEntry {
+    /// Name of the entry's collection, such as `posts`.
+    #[serde(rename = "type")]
+    pub content_type: String,
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub image_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover: Option<ResponsiveImage>,
+    pub tags: Vec<String>,
+}
@@ -0,0 +1,47 @@
+//! Responsive image metadata produced by the build script's image pipeline.
+
+use serde::{Deserialize, Serialize};
+
+/// Formats generated for every image, best first, as `(extension, mime type)`.
+pub const IMAGE_FORMATS: &[(&str, &str)] = &[("avif", "image/avif"), ("webp", "image/webp")];
+
+/// `sizes` for images in an article body, which is at most `max-w-3xl` (48rem) wide.
+pub const ARTICLE_IMAGE_SIZES: &str = "(min-width: 48rem) 48rem, 100vw";
+
+/// Resized variants of a local image, generated under `public/content/images/`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResponsiveImage {
+    /// Intrinsic size of the source image, used to reserve space before it loads.
+    pub width: u32,
+    pub height: u32,
+    /// Site-relative path of the variants, without the `-{width}.{ext}` suffix.
+    pub variants: String,
+    /// Widths available for every format in [`IMAGE_FORMATS`].
+    pub widths: Vec<u32>,
+    /// A tiny blurred preview as a `data:` URI, shown while the image loads.
+    pub placeholder: String,
+}
+
+impl ResponsiveImage {
+    /// `srcset` value listing every generated width of one format.
+    pub fn srcset(&self, base_path: &str, extension: &str) -> String {
+        self.widths
+            .iter()
+            .map(|width| {
+                format!(
+                    "{}/{}-{}.{} {}w",
+                    base_path, self.variants, width, extension, width
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Inline style showing the placeholder behind the image until it loads.
+    pub fn placeholder_style(&self) -> String {
+        format!(
+            "background-image: url('{}'); background-size: cover; background-position: center;",
+            self.placeholder
+        )
+    }
+}
@@ -0,0 +1,703 @@
+//! Allowlist sanitization of HTML trees, applied to every rendered entry body
+//! and to user-supplied text such as comments.
+//!
+//! Elements not in a [`Policy`] are unwrapped (their content is kept) unless
+//! they are listed in `drop_content`, in which case they go with everything
+//! inside them. Attributes not in the policy are removed, URLs must use an
+//! allowed scheme, and `style` keeps only allowed properties.
+//!
+//! The site's policies are set in `constants.rs`, starting from [`CONTENT`]
+//! and [`COMMENTS`].
+
+use crate::html::{parse_fragment, to_html, Node};
+
+/// What markup survives sanitization.
+#[derive(Clone, Copy, Debug)]
+pub struct Policy {
+    /// Elements kept as they are.
+    pub tags: &'static [&'static str],
+    /// Elements removed together with their content.
+    pub drop_content: &'static [&'static str],
+    /// Attributes allowed on every kept element. A trailing `*` matches a
+    /// prefix, as in `aria-*`.
+    pub attributes: &'static [&'static str],
+    /// Attributes allowed on specific elements only.
+    pub tag_attributes: &'static [(&'static str, &'static [&'static str])],
+    /// Schemes a URL attribute may use. Relative URLs are always allowed.
+    pub url_schemes: &'static [&'static str],
+    /// Whether images may be inlined as `data:image/...` URLs, such as the
+    /// blurred placeholders of responsive images.
+    pub data_images: bool,
+    /// CSS properties kept in `style` attributes.
+    pub style_properties: &'static [&'static str],
+    /// Whether shortcodes are kept to be mounted as components.
+    pub shortcodes: bool,
+    /// `rel` forced onto every link, replacing the author's.
+    pub link_rel: Option<&'static str>,
+}
+
+/// Attributes whose value is a URL.
+const URL_ATTRIBUTES: &[&str] = &[
+    "action",
+    "background",
+    "cite",
+    "data",
+    "formaction",
+    "href",
+    "longdesc",
+    "poster",
+    "src",
+    "xlink:href",
+];
+
+/// Elements that can run script, load other documents or restyle the page,
+/// and whose content makes no sense on its own.
+const DANGEROUS: &[&str] = &[
+    "annotation-xml",
+    "applet",
+    "base",
+    "embed",
+    "form",
+    "frame",
+    "frameset",
+    "iframe",
+    "link",
+    "meta",
+    "noembed",
+    "noframes",
+    "noscript",
+    "object",
+    "script",
+    "select",
+    "style",
+    "svg",
+    "template",
+    "textarea",
+    "title",
+    "xmp",
+];
+
+/// Attributes of the MathML elements `pulldown-latex` writes.
+const MATHML_ATTRIBUTES: &[&str] = &[
+    "accent",
+    "accentunder",
+    "columnalign",
+    "columnlines",
+    "columnspacing",
+    "depth",
+    "display",
+    "displaystyle",
+    "encoding",
+    "fence",
+    "form",
+    "height",
+    "largeop",
+    "linethickness",
+    "lspace",
+    "mathvariant",
+    "maxsize",
+    "minsize",
+    "movablelimits",
+    "notation",
+    "rowalign",
+    "rowlines",
+    "rowspacing",
+    "rspace",
+    "scriptlevel",
+    "separator",
+    "stretchy",
+    "style",
+    "symmetric",
+    "voffset",
+    "width",
+    "xmlns",
+];
+
+/// Markup rendered from an entry's markdown: everything the markdown writer,
+/// syntax highlighting, math, callouts and responsive images produce, plus the
+/// common HTML authors write by hand.
+pub const CONTENT: Policy = Policy {
+    tags: &[
+        "a",
+        "abbr",
+        "annotation",
+        "aside",
+        "audio",
+        "b",
+        "blockquote",
+        "br",
+        "caption",
+        "cite",
+        "code",
+        "col",
+        "colgroup",
+        "dd",
+        "del",
+        "details",
+        "dfn",
+        "div",
+        "dl",
+        "dt",
+        "em",
+        "figcaption",
+        "figure",
+        "h1",
+        "h2",
+        "h3",
+        "h4",
+        "h5",
+        "h6",
+        "hr",
+        "i",
+        "img",
+        "input",
+        "ins",
+        "kbd",
+        "li",
+        "mark",
+        "math",
+        "menclose",
+        "merror",
+        "mfrac",
+        "mi",
+        "mmultiscripts",
+        "mn",
+        "mo",
+        "mover",
+        "mpadded",
+        "mphantom",
+        "mprescripts",
+        "mroot",
+        "mrow",
+        "ms",
+        "mspace",
+        "msqrt",
+        "mstyle",
+        "msub",
+        "msubsup",
+        "msup",
+        "mtable",
+        "mtd",
+        "mtext",
+        "mtr",
+        "munder",
+        "munderover",
+        "none",
+        "ol",
+        "p",
+        "picture",
+        "pre",
+        "q",
+        "s",
+        "samp",
+        "semantics",
+        "small",
+        "source",
+        "span",
+        "strong",
+        "sub",
+        "summary",
+        "sup",
+        "table",
+        "tbody",
+        "td",
+        "tfoot",
+        "th",
+        "thead",
+        "time",
+        "tr",
+        "track",
+        "u",
+        "ul",
+        "var",
+        "video",
+        "wbr",
+    ],
+    drop_content: DANGEROUS,
+    attributes: &["aria-*", "class", "dir", "id", "lang", "role", "title"],
+    tag_attributes: &[
+        ("a", &["href", "rel", "target"]),
+        ("audio", &["controls", "loop", "muted", "preload", "src"]),
+        ("blockquote", &["cite"]),
+        ("col", &["span"]),
+        ("colgroup", &["span"]),
+        ("del", &["cite", "datetime"]),
+        ("details", &["open"]),
+        (
+            "img",
+            &[
+                "alt", "decoding", "height", "loading", "src", "srcset", "sizes", "style", "width",
+            ],
+        ),
+        ("input", &["checked", "disabled", "type"]),
+        ("ins", &["cite", "datetime"]),
+        ("ol", &["reversed", "start", "type"]),
+        ("q", &["cite"]),
+        ("source", &["media", "sizes", "src", "srcset", "type"]),
+        ("td", &["align", "colspan", "rowspan", "style"]),
+        ("th", &["align", "colspan", "rowspan", "scope", "style"]),
+        ("time", &["datetime"]),
+        ("track", &["default", "kind", "label", "src", "srclang"]),
+        (
+            "video",
+            &[
+                "controls",
+                "height",
+                "loop",
+                "muted",
+                "playsinline",
+                "poster",
+                "preload",
+                "src",
+                "width",
+            ],
+        ),
+        ("annotation", MATHML_ATTRIBUTES),
+        ("math", MATHML_ATTRIBUTES),
+        ("menclose", MATHML_ATTRIBUTES),
+        ("merror", MATHML_ATTRIBUTES),
+        ("mfrac", MATHML_ATTRIBUTES),
+        ("mi", MATHML_ATTRIBUTES),
+        ("mmultiscripts", MATHML_ATTRIBUTES),
+        ("mn", MATHML_ATTRIBUTES),
+        ("mo", MATHML_ATTRIBUTES),
+        ("mover", MATHML_ATTRIBUTES),
+        ("mpadded", MATHML_ATTRIBUTES),
+        ("mphantom", MATHML_ATTRIBUTES),
+        ("mroot", MATHML_ATTRIBUTES),
+        ("mrow", MATHML_ATTRIBUTES),
+        ("ms", MATHML_ATTRIBUTES),
+        ("mspace", MATHML_ATTRIBUTES),
+        ("msqrt", MATHML_ATTRIBUTES),
+        ("mstyle", MATHML_ATTRIBUTES),
+        ("msub", MATHML_ATTRIBUTES),
+        ("msubsup", MATHML_ATTRIBUTES),
+        ("msup", MATHML_ATTRIBUTES),
+        ("mtable", MATHML_ATTRIBUTES),
+        ("mtd", MATHML_ATTRIBUTES),
+        ("mtext", MATHML_ATTRIBUTES),
+        ("mtr", MATHML_ATTRIBUTES),
+        ("munder", MATHML_ATTRIBUTES),
+        ("munderover", MATHML_ATTRIBUTES),
+        ("semantics", MATHML_ATTRIBUTES),
+    ],
+    url_schemes: &["http", "https", "mailto", "tel"],
+    data_images: true,
+    style_properties: &[
+        "background-color",
+        "background-image",
+        "background-position",
+        "background-size",
+        "border",
+        "border-color",
+        "color",
+        "height",
+        "margin-left",
+        "text-align",
+    ],
+    shortcodes: true,
+    link_rel: None,
+};
+
+/// Text written by visitors: basic formatting and links, which search
+/// engines are told not to follow.
+pub const COMMENTS: Policy = Policy {
+    tags: &[
+        "a",
+        "b",
+        "blockquote",
+        "br",
+        "code",
+        "del",
+        "em",
+        "i",
+        "li",
+        "ol",
+        "p",
+        "pre",
+        "s",
+        "strong",
+        "ul",
+    ],
+    drop_content: DANGEROUS,
+    attributes: &[],
+    tag_attributes: &[("a", &["href", "title"])],
+    url_schemes: &["http", "https", "mailto"],
+    data_images: false,
+    style_properties: &[],
+    shortcodes: false,
+    link_rel: Some("nofollow ugc noopener noreferrer"),
+};
+
+/// Removes everything `policy` does not allow from a tree.
+pub fn sanitize(nodes: Vec<Node>, policy: &Policy) -> Vec<Node> {
+    let mut clean = Vec::with_capacity(nodes.len());
+    for node in nodes {
+        sanitize_node(node, policy, &mut clean);
+    }
+    clean
+}
+
+/// Parses, sanitizes and serializes an HTML fragment.
+pub fn sanitize_html(html: &str, policy: &Policy) -> String {
+    to_html(&sanitize(parse_fragment(html), policy))
+}
+
+fn sanitize_node(node: Node, policy: &Policy, clean: &mut Vec<Node>) {
+    let (tag, attrs, children) = match node {
+        Node::Text(_) => return clean.push(node),
+        Node::Shortcode(_) if policy.shortcodes => return clean.push(node),
+        Node::Shortcode(_) => return,
+        Node::Element {
+            tag,
+            attrs,
+            children,
+        } => (tag, attrs, children),
+    };
+    if policy.drop_content.contains(&tag.as_str()) {
+        return;
+    }
+    let children = sanitize(children, policy);
+    if !policy.tags.contains(&tag.as_str()) {
+        clean.extend(children);
+        return;
+    }
+
+    let mut attrs: Vec<(String, String)> = attrs
+        .into_iter()
+        .filter(|(name, _)| allows_attribute(policy, &tag, name))
+        .filter_map(|(name, value)| {
+            let value = match name.as_str() {
+                "style" => clean_style(&value, policy)?,
+                "srcset" => clean_srcset(&value, policy)?,
+                name if URL_ATTRIBUTES.contains(&name) => {
+                    allows_url(&value, policy).then_some(value)?
+                }
+                _ => value,
+            };
+            Some((name, value))
+        })
+        .collect();
+    if let (Some(rel), "a") = (policy.link_rel, tag.as_str()) {
+        attrs.retain(|(name, _)| name != "rel");
+        attrs.push(("rel".to_string(), rel.to_string()));
+    }
+    clean.push(Node::Element {
+        tag,
+        attrs,
+        children,
+    });
+}
+
+fn allows_attribute(policy: &Policy, tag: &str, name: &str) -> bool {
+    let matches = |allowed: &&str| match allowed.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => *allowed == name,
+    };
+    policy.attributes.iter().any(matches)
+        || policy
+            .tag_attributes
+            .iter()
+            .filter(|(allowed_tag, _)| *allowed_tag == tag)
+            .any(|(_, attributes)| attributes.iter().any(matches))
+}
+
+/// Whether a URL is relative or uses an allowed scheme. Browsers ignore
+/// whitespace and control characters inside a scheme, so they are ignored
+/// here too (`java\tscript:` is `javascript:`).
+fn allows_url(url: &str, policy: &Policy) -> bool {
+    let url: String = url
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect();
+    let Some(colon) = url.find(':') else {
+        return true;
+    };
+    let scheme = &url[..colon];
+    // A `/`, `?` or `#` before the colon makes it part of a relative path
+    if scheme.contains(['/', '?', '#']) {
+        return true;
+    }
+    if policy.data_images && scheme.eq_ignore_ascii_case("data") {
+        let media_type = url[colon + 1..].to_ascii_lowercase();
+        return [
+            "image/png",
+            "image/jpeg",
+            "image/gif",
+            "image/webp",
+            "image/avif",
+        ]
+        .iter()
+        .any(|allowed| media_type.starts_with(allowed));
+    }
+    policy
+        .url_schemes
+        .iter()
+        .any(|allowed| scheme.eq_ignore_ascii_case(allowed))
+}
+
+/// Checks every candidate URL of a `srcset`.
+fn clean_srcset(srcset: &str, policy: &Policy) -> Option<String> {
+    srcset
+        .split(',')
+        .all(|candidate| {
+            let url = candidate.split_whitespace().next().unwrap_or_default();
+            allows_url(url, policy)
+        })
+        .then(|| srcset.to_string())
+}
+
+/// Keeps the declarations of allowed properties whose values cannot run
+/// script or load anything but an allowed URL.
+fn clean_style(style: &str, policy: &Policy) -> Option<String> {
+    let declarations: Vec<String> = split_declarations(style)
+        .into_iter()
+        .filter_map(|declaration| {
+            let (property, value) = declaration.split_once(':')?;
+            let property = property.trim().to_ascii_lowercase();
+            let value = value.trim();
+            if !policy.style_properties.contains(&property.as_str()) {
+                return None;
+            }
+            let lower = value.to_ascii_lowercase();
+            // Escapes and comments can hide any of the checks below
+            if ["\\", "/*", "expression", "@import", "<", "&"]
+                .iter()
+                .any(|banned| lower.contains(banned))
+            {
+                return None;
+            }
+            let mut rest = lower.as_str();
+            while let Some(start) = rest.find("url(") {
+                let inner = &rest[start + 4..];
+                let end = inner.find(')')?;
+                let url = inner[..end].trim().trim_matches(['\'', '"']);
+                if !allows_url(url, policy) {
+                    return None;
+                }
+                rest = &inner[end..];
+            }
+            Some(format!("{}: {}", property, value))
+        })
+        .collect();
+    if declarations.is_empty() {
+        None
+    } else {
+        Some(declarations.join("; "))
+    }
+}
+
+/// Splits a `style` value at the semicolons outside quotes and parentheses,
+/// which `url(data:image/webp;base64,...)` contains.
+fn split_declarations(style: &str) -> Vec<&str> {
+    let mut declarations = Vec::new();
+    let mut quote = None;
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in style.char_indices() {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.saturating_sub(1),
+            (None, ';') if depth == 0 => {
+                declarations.push(&style[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    declarations.push(&style[start..]);
+    declarations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn content(html: &str) -> String {
+        sanitize_html(html, &CONTENT)
+    }
+
+    fn comment(html: &str) -> String {
+        sanitize_html(html, &COMMENTS)
+    }
+
+    #[test]
+    fn removes_scripts_with_their_content() {
+        assert_eq!(content("a<script>alert(1)</script>b"), "ab");
+        assert_eq!(content("<SCRIPT SRC=//evil.example/x.js></SCRIPT>ok"), "ok");
+        assert_eq!(content("<script/x>alert(1)</script>"), "");
+        assert_eq!(content("<style>body{display:none}</style>"), "");
+        assert_eq!(
+            content("<scr<script>ipt>alert(1)</script>"),
+            "ipt&gt;alert(1)"
+        );
+        assert_eq!(
+            content("<noscript><p title=\"</noscript><img src=x onerror=alert(1)>\"></noscript>"),
+            r#"<img src="x">&quot;&gt;"#
+        );
+    }
+
+    #[test]
+    fn removes_event_handlers() {
+        assert_eq!(
+            content(r#"<img src="x.png" onerror="alert(1)" alt="x">"#),
+            r#"<img src="x.png" alt="x">"#
+        );
+        assert_eq!(
+            content(r#"<p ONMOUSEOVER=alert(1) class="lead">hi</p>"#),
+            r#"<p class="lead">hi</p>"#
+        );
+        assert_eq!(
+            content(r#"<details open ontoggle=alert(1)>x</details>"#),
+            r#"<details open="">x</details>"#
+        );
+    }
+
+    #[test]
+    fn removes_script_urls() {
+        for href in [
+            "javascript:alert(1)",
+            "JaVaScRiPt:alert(1)",
+            " javascript:alert(1)",
+            "java\tscript:alert(1)",
+            "java&#x09;script:alert(1)",
+            "&#106;avascript:alert(1)",
+            "&#106avascript:alert(1)",
+            "&#x6A&#x61&#x76&#x61script:alert(1)",
+            "javascript&colon;alert(1)",
+            "\u{1}javascript:alert(1)",
+            "vbscript:msgbox(1)",
+            "data:text/html;base64,PHNjcmlwdD5hbGVydCgxKTwvc2NyaXB0Pg==",
+        ] {
+            let html = format!(r#"<a href="{}">x</a>"#, href.replace('"', "&quot;"));
+            assert_eq!(content(&html), "<a>x</a>", "{}", href);
+        }
+        assert_eq!(
+            content(r#"<img src="data:image/svg+xml,<svg onload=alert(1)>">"#),
+            "<img>"
+        );
+        assert_eq!(
+            content(r#"<img srcset="a.png 1x, javascript:alert(1) 2x">"#),
+            "<img>"
+        );
+    }
+
+    #[test]
+    fn keeps_safe_urls() {
+        for href in [
+            "https://example.com/?q=a:b",
+            "/blog/post",
+            "post.md#a:b",
+            "./a:b",
+            "//example.com",
+            "mailto:hi@example.com",
+        ] {
+            let html = format!(r#"<a href="{}">x</a>"#, href);
+            assert_eq!(content(&html), html);
+        }
+        let picture = r#"<img src="a.png" srcset="a-480.webp 480w, a-960.webp 960w" style="background-image: url('data:image/webp;base64,AAAA'); background-size: cover">"#;
+        assert_eq!(content(picture), picture);
+    }
+
+    #[test]
+    fn filters_styles() {
+        assert_eq!(
+            content(r#"<td style="text-align: center; position: fixed">x</td>"#),
+            r#"<td style="text-align: center">x</td>"#
+        );
+        assert_eq!(
+            content(r#"<td style="text-align: expression(alert(1))">x</td>"#),
+            "<td>x</td>"
+        );
+        assert_eq!(
+            content(r#"<img style="background-image: url(javascript:alert(1))">"#),
+            "<img>"
+        );
+        assert_eq!(
+            content(r#"<img style="background-image: u\72l(https://evil.example)">"#),
+            "<img>"
+        );
+        assert_eq!(content(r#"<p style="color: red">x</p>"#), "<p>x</p>");
+    }
+
+    #[test]
+    fn removes_embedded_documents() {
+        assert_eq!(
+            content(r#"<iframe src="javascript:alert(1)"></iframe>"#),
+            ""
+        );
+        assert_eq!(
+            content(r#"<object data="x.swf"><embed src="x.swf"></object>"#),
+            ""
+        );
+        assert_eq!(
+            content("<svg><script>alert(1)</script></svg>after"),
+            "after"
+        );
+        assert_eq!(content(r#"<svg onload="alert(1)"/>x"#), "x");
+        assert_eq!(
+            content(r#"<meta http-equiv="refresh" content="0;url=//evil.example">"#),
+            ""
+        );
+        assert_eq!(content(r#"<base href="//evil.example/">"#), "");
+        assert_eq!(
+            content(r#"<form action="//evil.example"><input name="password"></form>"#),
+            ""
+        );
+        assert_eq!(
+            content(
+                r#"<math><annotation-xml encoding="text/html"><img src=x onerror=alert(1)></annotation-xml></math>"#
+            ),
+            "<math></math>"
+        );
+    }
+
+    #[test]
+    fn unwraps_unknown_elements() {
+        assert_eq!(content("<blink><b>x</b></blink>"), "<b>x</b>");
+        assert_eq!(
+            content("<b><i>x</b><script>alert(1)</i></script>y"),
+            "<b><i>x</i></b>y"
+        );
+        assert_eq!(
+            content("<p>1 < 2 & 3 > 2</p>"),
+            "<p>1 &lt; 2 &amp; 3 &gt; 2</p>"
+        );
+        assert_eq!(content("<!--><script>alert(1)</script>-->x"), "x");
+    }
+
+    #[test]
+    fn keeps_rendered_markup() {
+        let html = concat!(
+            r#"<aside class="callout callout-note" role="note"><p class="callout-title">"#,
+            r#"<span class="material-symbols-outlined callout-icon" aria-hidden="true">info</span>"#,
+            r#"<span>Note</span></p><div class="callout-body"><p>x</p></div></aside>"#,
+            r#"<pre><code class="language-rust"><span class="hl-keyword">fn</span></code></pre>"#,
+            r#"<math display="block"><semantics><mi mathvariant="normal">x</mi>"#,
+            r#"<annotation encoding="application/x-tex">x</annotation></semantics></math>"#,
+            r#"<mstyle displaystyle="false" scriptlevel="1" style="color: rgb(255 0 0)"><mi>y</mi></mstyle>"#,
+            r#"<table><tr><td style="text-align: right">1</td></tr></table>"#,
+            r#"<!--{{< post id="a" >}}-->"#,
+        );
+        assert_eq!(content(html), html);
+    }
+
+    #[test]
+    fn restricts_comments() {
+        assert_eq!(
+            comment(r#"<a href="https://example.com" rel="author" class="x">hi</a>"#),
+            r#"<a href="https://example.com" rel="nofollow ugc noopener noreferrer">hi</a>"#
+        );
+        assert_eq!(
+            comment(r#"<h1 id="x">Big</h1><img src="x.png"><!--{{< post id="a" >}}-->"#),
+            "Big"
+        );
+        assert_eq!(
+            comment(r#"<p>Hi <a href="javascript:alert(1)">there</a></p>"#),
+            r#"<p>Hi <a rel="nofollow ugc noopener noreferrer">there</a></p>"#
+        );
+    }
+}
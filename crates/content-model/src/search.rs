@@ -0,0 +1,289 @@
+//! The full-text search index: written by the build script to
+//! `public/content/search_index.json` and queried by the app.
+//!
+//! It is an inverted index mapping each [tokenized](crate::tokenizer) term to
+//! `[doc, weight]` postings, plus the start of every document's plain text so
+//! the app can cut snippet excerpts without the index growing with the site.
+//! Terms are weighted by the field they appear in.
+
+use crate::tokenizer::{stem, tokenize};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+pub const TITLE_WEIGHT: u32 = 10;
+pub const TAG_WEIGHT: u32 = 6;
+pub const HEADING_WEIGHT: u32 = 4;
+pub const BODY_WEIGHT: u32 = 1;
+
+/// Characters of context kept before and after a match in a snippet.
+const SNIPPET_BEFORE: usize = 60;
+const SNIPPET_AFTER: usize = 140;
+/// Characters of each body kept for snippets; matches further in are found
+/// but shown without one.
+const SNIPPET_SOURCE: usize = 1000;
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct SearchDoc {
+    #[serde(rename = "type")]
+    content_type: String,
+    id: String,
+    text: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct SearchIndex {
+    docs: Vec<SearchDoc>,
+    terms: BTreeMap<String, Vec<(usize, u32)>>,
+}
+
+/// A ranked match for a search query.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchHit {
+    pub id: String,
+    pub score: f32,
+    /// Excerpt of the body around the first match, if the body matched.
+    pub snippet: Option<String>,
+}
+
+impl SearchIndex {
+    /// Indexes an entry from its fields, each `(text, weight)`, keeping the
+    /// start of `body` for snippets.
+    pub fn add(&mut self, content_type: &str, id: &str, fields: &[(&str, u32)], body: &str) {
+        let mut weights: BTreeMap<String, u32> = BTreeMap::new();
+        for (text, weight) in fields {
+            for term in tokenize(text) {
+                *weights.entry(term).or_default() += weight;
+            }
+        }
+
+        let doc = self.docs.len();
+        for (term, weight) in weights {
+            self.terms.entry(term).or_default().push((doc, weight));
+        }
+        self.docs.push(SearchDoc {
+            content_type: content_type.to_string(),
+            id: id.to_string(),
+            text: snippet_source(body).to_string(),
+        });
+    }
+
+    /// Returns entries of `content_type` matching every query term, best first.
+    /// The last term also matches as a prefix so results update while typing.
+    pub fn search(&self, query: &str, content_type: &str) -> Vec<SearchHit> {
+        let terms = tokenize(query);
+        if terms.is_empty() {
+            return Vec::new();
+        }
+
+        let total = self.docs.len() as f32;
+        let mut scores: HashMap<usize, (f32, usize)> = HashMap::new();
+        for (i, term) in terms.iter().enumerate() {
+            let is_last = i == terms.len() - 1;
+            let mut matched: HashMap<usize, f32> = HashMap::new();
+            // Terms are sorted, so the ones a prefix matches follow it directly
+            let candidates = self
+                .terms
+                .range::<String, _>(term..)
+                .take_while(|(indexed, _)| {
+                    *indexed == term || (is_last && indexed.starts_with(term.as_str()))
+                });
+            for (_, postings) in candidates {
+                let idf = (1.0 + total / postings.len() as f32).ln();
+                for &(doc, weight) in postings {
+                    let score = matched.entry(doc).or_default();
+                    *score = score.max(weight as f32 * idf);
+                }
+            }
+            for (doc, score) in matched {
+                let entry = scores.entry(doc).or_default();
+                entry.0 += score;
+                entry.1 += 1;
+            }
+        }
+
+        let words: Vec<String> = query
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !tokenize(word).is_empty())
+            .map(str::to_ascii_lowercase)
+            .collect();
+
+        let mut hits: Vec<SearchHit> = scores
+            .into_iter()
+            .filter(|(_, (_, count))| *count == terms.len())
+            .filter_map(|(doc, (score, _))| {
+                let doc = self.docs.get(doc)?;
+                (doc.content_type == content_type).then(|| SearchHit {
+                    id: doc.id.clone(),
+                    score,
+                    snippet: snippet(&doc.text, &words),
+                })
+            })
+            .collect();
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits
+    }
+}
+
+/// The first [`SNIPPET_SOURCE`] characters of `body`, ending on a word boundary.
+fn snippet_source(body: &str) -> &str {
+    let Some((end, _)) = body.char_indices().nth(SNIPPET_SOURCE) else {
+        return body;
+    };
+    body[..end]
+        .rfind(' ')
+        .map_or(&body[..end], |space| &body[..space])
+}
+
+/// Cuts an excerpt of `text` around the first occurrence of any query word,
+/// ending on word boundaries.
+fn snippet(text: &str, words: &[String]) -> Option<String> {
+    // ASCII lowercasing keeps byte offsets, so positions found here index `text`
+    let haystack = text.to_ascii_lowercase();
+    let pos = words
+        .iter()
+        .filter_map(|word| {
+            haystack
+                .find(word.as_str())
+                .or_else(|| haystack.find(&stem(word)))
+        })
+        .min()?;
+
+    let mut start = pos.saturating_sub(SNIPPET_BEFORE);
+    while !text.is_char_boundary(start) {
+        start -= 1;
+    }
+    if start > 0 {
+        start = text[start..pos]
+            .find(' ')
+            .map_or(start, |space| start + space + 1);
+    }
+    let mut end = (pos + SNIPPET_AFTER).min(text.len());
+    while !text.is_char_boundary(end) {
+        end += 1;
+    }
+    if end < text.len() {
+        end = text[pos..end].rfind(' ').map_or(end, |space| pos + space);
+    }
+
+    let prefix = if start > 0 { "…" } else { "" };
+    let suffix = if end < text.len() { "…" } else { "" };
+    Some(format!("{}{}{}", prefix, text[start..end].trim(), suffix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index() -> SearchIndex {
+        let mut index = SearchIndex::default();
+        index.add(
+            "posts",
+            "in-body",
+            &[
+                ("Notes", TITLE_WEIGHT),
+                ("A word on rust and wasm.", BODY_WEIGHT),
+            ],
+            "A word on rust and wasm.",
+        );
+        index.add(
+            "posts",
+            "in-title",
+            &[("Rust", TITLE_WEIGHT), ("Nothing else here.", BODY_WEIGHT)],
+            "Nothing else here.",
+        );
+        index.add(
+            "posts",
+            "in-tags",
+            &[("Tooling", TITLE_WEIGHT), ("rust", TAG_WEIGHT)],
+            "",
+        );
+        index.add(
+            "projects",
+            "other-collection",
+            &[("Rust", TITLE_WEIGHT)],
+            "",
+        );
+        index
+    }
+
+    fn ids(hits: &[SearchHit]) -> Vec<&str> {
+        hits.iter().map(|hit| hit.id.as_str()).collect()
+    }
+
+    #[test]
+    fn ranks_by_field_weight() {
+        let hits = index().search("Rust", "posts");
+        assert_eq!(ids(&hits), ["in-title", "in-tags", "in-body"]);
+        assert!(hits.windows(2).all(|pair| pair[0].score > pair[1].score));
+    }
+
+    #[test]
+    fn requires_every_term_and_completes_the_last() {
+        let index = index();
+        assert_eq!(ids(&index.search("rust wasm", "posts")), ["in-body"]);
+        assert_eq!(ids(&index.search("rust wa", "posts")), ["in-body"]);
+        assert!(index.search("rust python", "posts").is_empty());
+        assert!(index.search("the and", "posts").is_empty());
+        assert_eq!(ids(&index.search("rust", "projects")), ["other-collection"]);
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let json = serde_json::to_string(&index()).unwrap();
+        let index: SearchIndex = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            ids(&index.search("rust", "posts")),
+            ["in-title", "in-tags", "in-body"]
+        );
+    }
+
+    #[test]
+    fn snippets_cut_around_the_first_match() {
+        let hits = index().search("wasm", "posts");
+        assert_eq!(hits[0].snippet.as_deref(), Some("A word on rust and wasm."));
+
+        let text = format!("{} needle {}", "lead ".repeat(30), "tail ".repeat(60));
+        let excerpt = snippet(&text, &["needle".to_string()]).unwrap();
+        assert!(excerpt.starts_with("…lead"));
+        assert!(excerpt.ends_with("tail…"));
+        assert!(excerpt.contains("needle"));
+        assert!(excerpt.chars().count() <= SNIPPET_BEFORE + SNIPPET_AFTER + 2);
+        assert_eq!(snippet(&text, &["missing".to_string()]), None);
+    }
+
+    #[test]
+    fn keeps_only_the_start_of_long_bodies() {
+        let body = format!(
+            "{}needle {}haystack",
+            "word ".repeat(100),
+            "more ".repeat(300)
+        );
+        let mut index = SearchIndex::default();
+        index.add("posts", "long", &[(&body, BODY_WEIGHT)], &body);
+        let stored = &index.docs[0].text;
+        assert!(stored.chars().count() <= SNIPPET_SOURCE);
+        assert!(body.starts_with(stored.as_str()) && stored.ends_with("more"));
+
+        let hits = index.search("haystack", "posts");
+        assert_eq!(ids(&hits), ["long"]);
+        assert_eq!(hits[0].snippet, None);
+        assert!(index.search("needle", "posts")[0].snippet.is_some());
+    }
+
+    #[test]
+    fn snippets_stay_on_char_boundaries() {
+        let text = format!("{}러스트 needle {}", "가 ".repeat(40), "라 ".repeat(60));
+        // The context on both sides of the match ends inside a three-byte character
+        let pos = text.find("needle").unwrap();
+        assert!(!text.is_char_boundary(pos - SNIPPET_BEFORE));
+        assert!(!text.is_char_boundary(pos + SNIPPET_AFTER));
+        for word in ["needle", "러스트", "가", "라"] {
+            let excerpt = snippet(&text, &[word.to_string()]).unwrap();
+            assert!(excerpt.contains(word), "{}", word);
+        }
+        let excerpt = snippet(&text, &["needle".to_string()]).unwrap();
+        assert!(excerpt.starts_with('…') && excerpt.ends_with('…'));
+        // Each end moves at most one character to reach a boundary
+        assert!(excerpt.len() <= SNIPPET_BEFORE + SNIPPET_AFTER + 2 * '…'.len_utf8() + 6);
+    }
+}
@@ -0,0 +1,64 @@
+//! Multi-part post series.
+
+use crate::date::Date;
+use serde::{Deserialize, Serialize};
+
+/// A post's place in a series, declared in frontmatter:
+///
+/// ```yaml
+/// series:
+///   id: dioxus-from-scratch
+///   part: 2
+///   title: Dioxus from Scratch # optional, on any one part
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SeriesRef {
+    pub id: String,
+    pub part: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+/// One entry of a series, as listed in `series_index.json`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SeriesPart {
+    /// Name of the collection the entry belongs to.
+    pub collection: String,
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub date: Date,
+    pub part: u32,
+}
+
+/// A series with its entries in reading order.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Series {
+    pub id: String,
+    pub title: String,
+    pub parts: Vec<SeriesPart>,
+}
+
+impl Series {
+    /// Index of an entry in [`Series::parts`].
+    pub fn position(&self, collection: &str, id: &str) -> Option<usize> {
+        self.parts
+            .iter()
+            .position(|part| part.collection == collection && part.id == id)
+    }
+
+    /// The parts before and after an entry, if any.
+    pub fn neighbours(
+        &self,
+        collection: &str,
+        id: &str,
+    ) -> (Option<&SeriesPart>, Option<&SeriesPart>) {
+        match self.position(collection, id) {
+            Some(idx) => (
+                idx.checked_sub(1).and_then(|prev| self.parts.get(prev)),
+                self.parts.get(idx + 1),
+            ),
+            None => (None, None),
+        }
+    }
+}
@@ -0,0 +1,246 @@
+//! Shortcodes: `{{< name key="value" >}}` on a line of its own in an entry's
+//! markdown, replaced by an app component when the entry is displayed.
+//!
+//! Rendering leaves a [`marker`] comment where the shortcode was. The document
+//! tree the app displays turns it into a shortcode node, mounted as the
+//! component registered under the shortcode's name; feeds and other plain-HTML
+//! consumers simply ignore the comment.
+
+use crate::collection::Collection;
+
+/// A parsed shortcode: its name and its arguments in source order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shortcode {
+    pub name: String,
+    pub args: Vec<(String, String)>,
+}
+
+/// The arguments a registered shortcode takes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShortcodeSpec {
+    pub name: &'static str,
+    pub required: &'static [&'static str],
+    pub optional: &'static [&'static str],
+}
+
+/// The entry card every collection gets as a shortcode named after its
+/// `singular`, such as `{{< post id="…" >}}`.
+pub const ENTRY_CARD: ShortcodeSpec = ShortcodeSpec {
+    name: "entry",
+    required: &["id"],
+    optional: &[],
+};
+
+/// Every other shortcode the app can display. Names are matched to components
+/// in `src/components/shortcodes.rs`; the build warns about anything else.
+pub const SHORTCODES: &[ShortcodeSpec] = &[
+    ShortcodeSpec {
+        name: "youtube",
+        required: &["id"],
+        optional: &["title", "start"],
+    },
+    ShortcodeSpec {
+        name: "github",
+        required: &["repo"],
+        optional: &["description"],
+    },
+    ShortcodeSpec {
+        name: "timeline",
+        required: &["date", "title"],
+        optional: &["description"],
+    },
+];
+
+/// Whether a paragraph's text is a shortcode rather than prose.
+pub fn is_shortcode(text: &str) -> bool {
+    let text = text.trim();
+    text.starts_with("{{<") && text.ends_with(">}}")
+}
+
+impl Shortcode {
+    /// Parses `{{< name key="value" other='value' flag=bare >}}`.
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let inner = source
+            .trim()
+            .strip_prefix("{{<")
+            .and_then(|rest| rest.strip_suffix(">}}"))
+            .ok_or_else(|| "a shortcode must be written as `{{< name ... >}}`".to_string())?;
+        let inner = inner.trim();
+        let name_end = inner.find(char::is_whitespace).unwrap_or(inner.len());
+        let (name, mut rest) = inner.split_at(name_end);
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(format!("invalid shortcode name `{}`", name));
+        }
+
+        let mut args = Vec::new();
+        loop {
+            rest = rest.trim_start();
+            if rest.is_empty() {
+                break;
+            }
+            let (key, after) = rest
+                .split_once('=')
+                .ok_or_else(|| format!("expected `key=\"value\"` at `{}`", rest))?;
+            let key = key.trim();
+            if key.is_empty() || key.contains(char::is_whitespace) {
+                return Err(format!("expected `key=\"value\"` at `{}`", rest));
+            }
+            let (value, after) = match after.chars().next() {
+                Some(quote @ ('"' | '\'')) => after[1..]
+                    .split_once(quote)
+                    .ok_or_else(|| format!("unclosed quote in `{}`", key))?,
+                _ => after.split_at(after.find(char::is_whitespace).unwrap_or(after.len())),
+            };
+            if args.iter().any(|(k, _)| k == key) {
+                return Err(format!("`{}` is given twice", key));
+            }
+            args.push((key.to_string(), value.to_string()));
+            rest = after;
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            args,
+        })
+    }
+
+    pub fn arg(&self, key: &str) -> Option<&str> {
+        self.args
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The collection of `collections` an entry card shortcode points into:
+    /// `project` and `post` name the `singular` of a collection.
+    pub fn entry_collection<'a>(&self, collections: &'a [Collection]) -> Option<&'a Collection> {
+        collections
+            .iter()
+            .find(|collection| collection.singular == self.name)
+    }
+
+    /// Checks the shortcode against [`ENTRY_CARD`] (for the `singular` of one
+    /// of `collections`) or [`SHORTCODES`].
+    pub fn validate(&self, collections: &[Collection]) -> Result<&'static ShortcodeSpec, String> {
+        let spec = match self.entry_collection(collections) {
+            Some(_) => &ENTRY_CARD,
+            None => SHORTCODES
+                .iter()
+                .find(|spec| spec.name == self.name)
+                .ok_or_else(|| format!("unknown shortcode `{}`", self.name))?,
+        };
+        if let Some(missing) = spec.required.iter().find(|key| self.arg(key).is_none()) {
+            return Err(format!("`{}` needs a `{}` argument", self.name, missing));
+        }
+        if let Some((key, _)) = self.args.iter().find(|(key, _)| {
+            !spec.required.contains(&key.as_str()) && !spec.optional.contains(&key.as_str())
+        }) {
+            return Err(format!("`{}` has no `{}` argument", self.name, key));
+        }
+        Ok(spec)
+    }
+}
+
+/// The HTML comment left in place of a shortcode. `%` and `-` in the source
+/// are percent-encoded, so an argument such as `caption="a --> b"` cannot end
+/// the comment early.
+pub fn marker(source: &str) -> String {
+    let mut encoded = String::with_capacity(source.len());
+    for c in source.trim().chars() {
+        match c {
+            '%' => encoded.push_str("%25"),
+            '-' => encoded.push_str("%2D"),
+            c => encoded.push(c),
+        }
+    }
+    format!("<!--{}-->", encoded)
+}
+
+/// The shortcode source held by the text of a comment written by [`marker`],
+/// or `None` for any other comment.
+pub fn parse_marker(comment: &str) -> Option<String> {
+    let mut source = String::with_capacity(comment.len());
+    let mut rest = comment.trim();
+    while let Some(idx) = rest.find('%') {
+        source.push_str(&rest[..idx]);
+        match rest.get(idx + 1..idx + 3) {
+            Some("25") => source.push('%'),
+            Some("2D") => source.push('-'),
+            _ => return None,
+        }
+        rest = &rest[idx + 3..];
+    }
+    source.push_str(rest);
+    is_shortcode(&source).then_some(source)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collection::TEST_COLLECTIONS;
+
+    #[test]
+    fn parses_arguments() {
+        let shortcode =
+            Shortcode::parse(r#"{{< youtube id="dQw4w9WgXcQ" title='A "talk"' start=42 >}}"#)
+                .unwrap();
+        assert_eq!(shortcode.name, "youtube");
+        assert_eq!(shortcode.arg("id"), Some("dQw4w9WgXcQ"));
+        assert_eq!(shortcode.arg("title"), Some(r#"A "talk""#));
+        assert_eq!(shortcode.arg("start"), Some("42"));
+        assert_eq!(shortcode.arg("missing"), None);
+        assert!(shortcode.validate(TEST_COLLECTIONS).is_ok());
+    }
+
+    #[test]
+    fn rejects_malformed_shortcodes() {
+        assert!(Shortcode::parse("{{< >}}").is_err());
+        assert!(Shortcode::parse(r#"{{< project id="x >}}"#).is_err());
+        assert!(Shortcode::parse("{{< project id >}}").is_err());
+        assert!(Shortcode::parse(r#"{{< project id="a" id="b" >}}"#).is_err());
+    }
+
+    #[test]
+    fn validates_against_registry() {
+        let unknown = Shortcode::parse("{{< carousel >}}").unwrap();
+        assert_eq!(
+            unknown.validate(TEST_COLLECTIONS).unwrap_err(),
+            "unknown shortcode `carousel`"
+        );
+        let missing = Shortcode::parse("{{< project >}}").unwrap();
+        assert!(missing
+            .validate(TEST_COLLECTIONS)
+            .unwrap_err()
+            .contains("`id`"));
+        let extra = Shortcode::parse(r#"{{< project id="a" size="big" >}}"#).unwrap();
+        assert!(extra
+            .validate(TEST_COLLECTIONS)
+            .unwrap_err()
+            .contains("`size`"));
+    }
+
+    #[test]
+    fn recognizes_shortcode_paragraphs() {
+        let source = r#"{{< project id="blog" >}}"#;
+        assert!(is_shortcode(source));
+        assert!(is_shortcode(&format!("  {}\n", source)));
+        assert!(!is_shortcode("{{ not one }}"));
+        assert_eq!(marker(source), format!("<!--{}-->", source));
+    }
+
+    #[test]
+    fn markers_cannot_end_the_comment_early() {
+        let source = r#"{{< youtube id="a-b" title="50% --> done" >}}"#;
+        let marker = marker(source);
+        assert_eq!(marker.matches("-->").count(), 1);
+        assert!(marker.ends_with("-->"));
+        let comment = marker
+            .strip_prefix("<!--")
+            .unwrap()
+            .strip_suffix("-->")
+            .unwrap();
+        assert_eq!(parse_marker(comment).as_deref(), Some(source));
+        assert_eq!(parse_marker(" a plain comment "), None);
+        assert_eq!(parse_marker("{{< post id=%zz >}}"), None);
+    }
+}
@@ -0,0 +1,77 @@
+//! URL segments derived from free text.
+
+use std::collections::HashSet;
+
+/// Lowercase, URL-safe form of a name (`"GitHub Pages"` -> `"github-pages"`).
+/// Letters of any script are kept, so `"러스트 입문"` becomes `"러스트-입문"`.
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.trim().chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_matches('-').to_string()
+}
+
+/// Hands out unique heading ids within one document. A repeated slug gets
+/// `-1`, `-2`, ... appended, and headings without letters become `section`.
+#[derive(Debug, Default)]
+pub struct Slugger {
+    used: HashSet<String>,
+}
+
+impl Slugger {
+    pub fn slug(&mut self, text: &str) -> String {
+        let mut base = slugify(text);
+        if base.is_empty() {
+            base = "section".to_string();
+        }
+        let mut slug = base.clone();
+        let mut n = 0;
+        while !self.used.insert(slug.clone()) {
+            n += 1;
+            slug = format!("{}-{}", base, n);
+        }
+        slug
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lowercases_and_joins_words() {
+        assert_eq!(slugify("GitHub Pages"), "github-pages");
+        assert_eq!(slugify("github-pages"), "github-pages");
+        assert_eq!(slugify("  github   pages "), "github-pages");
+    }
+
+    #[test]
+    fn collapses_punctuation() {
+        assert_eq!(slugify("C++ & Rust!"), "c-rust");
+        assert_eq!(slugify("WebAssembly (Wasm)"), "webassembly-wasm");
+        assert_eq!(slugify("---"), "");
+    }
+
+    #[test]
+    fn keeps_non_latin_letters() {
+        assert_eq!(slugify("러스트 입문"), "러스트-입문");
+        assert_eq!(slugify("Dioxus 0.7"), "dioxus-0-7");
+    }
+
+    #[test]
+    fn slugger_deduplicates() {
+        let mut slugger = Slugger::default();
+        assert_eq!(slugger.slug("Setup"), "setup");
+        assert_eq!(slugger.slug("Setup"), "setup-1");
+        assert_eq!(slugger.slug("setup 1"), "setup-1-1");
+        assert_eq!(slugger.slug("SETUP"), "setup-2");
+        assert_eq!(slugger.slug("🚀"), "section");
+        assert_eq!(slugger.slug("!!"), "section-1");
+        assert_eq!(slugger.slug("🎨 Phase 1: 설계"), "phase-1-설계");
+    }
+}
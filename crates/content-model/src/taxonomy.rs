@@ -0,0 +1,32 @@
+//! Tag taxonomy written by the build script to `tags_index.json`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A canonical tag with the listed entries that use it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TagInfo {
+    /// URL segment of the tag page, see [`slugify`](crate::slug::slugify).
+    pub slug: String,
+    /// Display name, from `tags.yaml` or the first spelling seen in content.
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Any CSS color, used for the tag in the tag cloud.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// Ids of the entries tagged with it, by collection name.
+    pub entries: BTreeMap<String, Vec<String>>,
+}
+
+impl TagInfo {
+    /// Number of entries tagged with it.
+    pub fn count(&self) -> usize {
+        self.entries.values().map(Vec::len).sum()
+    }
+
+    /// Ids of the entries of one collection tagged with it.
+    pub fn entries_in(&self, collection: &str) -> &[String] {
+        self.entries.get(collection).map_or(&[], Vec::as_slice)
+    }
+}
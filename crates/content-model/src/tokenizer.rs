@@ -0,0 +1,139 @@
+//! Tokenizer and stemmer shared by the search index builder and the runtime
+//! search, which must agree exactly on every term.
+
+/// Words too common to be worth indexing.
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "but", "by", "for", "from", "has", "have", "in",
+    "is", "it", "its", "of", "on", "or", "that", "the", "this", "to", "was", "we", "were", "will",
+    "with", "you", "your",
+];
+
+/// Splits text into lowercase, stemmed search terms, dropping stop words.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .filter(|word| word.chars().count() > 1 && !STOP_WORDS.contains(&word.as_str()))
+        .map(|word| stem(&word))
+        .collect()
+}
+
+/// A light suffix-stripping stemmer for English words: plurals first, then
+/// `-ing`, `-ed` and `-ly` when enough of a word is left. Non-ASCII words
+/// (e.g. Korean) are returned unchanged.
+pub fn stem(word: &str) -> String {
+    if !word.is_ascii() {
+        return word.to_string();
+    }
+    let len = word.len();
+    let word = if len > 4 && word.ends_with("ies") {
+        format!("{}y", &word[..len - 3])
+    } else if word.ends_with("sses") {
+        word[..len - 2].to_string()
+    } else if len > 3
+        && word.ends_with('s')
+        && !["ss", "us", "is"].iter().any(|s| word.ends_with(s))
+    {
+        word[..len - 1].to_string()
+    } else {
+        word.to_string()
+    };
+
+    let stripped = ["ing", "ed", "ly"].iter().find_map(|suffix| {
+        let stem = word.strip_suffix(suffix)?;
+        let keep = match *suffix {
+            // "speed", "need"
+            "ed" => stem.ends_with('e'),
+            // "apply", "family"
+            "ly" => stem.len() < 4 || stem.ends_with('i'),
+            _ => false,
+        };
+        // "string", "thing": what is left must still be a word
+        (stem.len() >= 3 && has_vowel(stem) && !keep).then_some(stem)
+    });
+    match stripped {
+        // "running" and "stopped" share their stem with "run" and "stop"
+        Some(stem) if stem.len() >= 4 && ends_with_double_consonant(stem) => {
+            stem[..stem.len() - 1].to_string()
+        }
+        Some(stem) => stem.to_string(),
+        None => word,
+    }
+}
+
+fn has_vowel(stem: &str) -> bool {
+    stem.chars()
+        .enumerate()
+        .any(|(i, c)| matches!(c, 'a' | 'e' | 'i' | 'o' | 'u') || (c == 'y' && i > 0))
+}
+
+/// A doubled final consonant other than `l`, `s` and `z`, which words such as
+/// "fall", "pass" and "buzz" end with anyway.
+fn ends_with_double_consonant(stem: &str) -> bool {
+    let bytes = stem.as_bytes();
+    let [.., a, b] = bytes else {
+        return false;
+    };
+    a == b && !b"aeiouylsz".contains(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_common_suffixes() {
+        for (word, expected) in [
+            ("posts", "post"),
+            ("studies", "study"),
+            ("classes", "class"),
+            ("testing", "test"),
+            ("tested", "test"),
+            ("quickly", "quick"),
+            ("running", "run"),
+            ("stopped", "stop"),
+            ("falling", "fall"),
+            ("thinkings", "think"),
+            ("analysis", "analysis"),
+        ] {
+            assert_eq!(stem(word), expected, "{}", word);
+        }
+    }
+
+    #[test]
+    fn keeps_words_that_only_look_suffixed() {
+        for word in [
+            "string", "strings", "thing", "spring", "bring", "speed", "need", "apply", "family",
+            "early", "add", "bus",
+        ] {
+            let expected = word
+                .strip_suffix('s')
+                .filter(|_| word.len() > 3)
+                .unwrap_or(word);
+            assert_eq!(stem(word), expected, "{}", word);
+        }
+        assert_eq!(stem("adding"), "add");
+        assert_eq!(stem("families"), stem("family"));
+        assert_eq!(stem("한국어"), "한국어");
+    }
+
+    #[test]
+    fn stems_are_stable() {
+        let text = "Strings, things and families: running tests quickly, stopped speeds, \
+                    added classes, studies of buses and analysis in 러스트";
+        let terms = tokenize(text);
+        assert!(terms.contains(&"string".to_string()));
+        assert!(!terms.iter().any(|term| term == "str"));
+        // Stemming a term again changes nothing, so the index and queries agree
+        assert_eq!(tokenize(&terms.join(" ")), terms);
+    }
+
+    #[test]
+    fn tokenizes_lowercase_terms_without_stop_words() {
+        assert_eq!(
+            tokenize("The Rust-powered WASM apps, and a 러스트 guide!"),
+            ["rust", "power", "wasm", "app", "러스트", "guide"]
+        );
+        assert!(tokenize("a an the of to x").is_empty());
+    }
+}
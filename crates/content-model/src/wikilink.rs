@@ -0,0 +1,151 @@
+//! Wiki-style cross-references between entries: `[[id]]`, `[[project:id]]`
+//! and `[[id|label]]`.
+//!
+//! Links name an entry by id, optionally prefixed with the `singular` or name
+//! of its collection, and resolve against the collection indexes rather than a
+//! path, so they survive base path and route changes. Without a label the
+//! link shows the target's title.
+
+use crate::collection::Collection;
+
+/// An entry a wiki link can point to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinkTarget {
+    pub collection: &'static Collection,
+    pub id: String,
+    pub title: String,
+}
+
+impl LinkTarget {
+    /// The entry's page, relative to the site root.
+    pub fn path(&self) -> String {
+        format!("/{}/{}", self.collection.route, self.id)
+    }
+}
+
+/// Every entry wiki links can resolve to.
+#[derive(Clone, Debug)]
+pub struct LinkTargets {
+    /// The collections a `prefix:` may name.
+    collections: &'static [Collection],
+    targets: Vec<LinkTarget>,
+}
+
+impl LinkTargets {
+    pub fn new(collections: &'static [Collection]) -> Self {
+        Self {
+            collections,
+            targets: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, collection: &'static Collection, id: &str, title: &str) {
+        self.targets.push(LinkTarget {
+            collection,
+            id: id.to_string(),
+            title: title.to_string(),
+        });
+    }
+
+    /// Finds the entry a link's target (the part before any `|`) names.
+    pub fn resolve(&self, reference: &str) -> Result<&LinkTarget, String> {
+        let (collection, id) = parse(self.collections, reference)?;
+        let mut matches = self.targets.iter().filter(|target| {
+            target.id == id && collection.is_none_or(|collection| target.collection == collection)
+        });
+        let Some(target) = matches.next() else {
+            return Err(match collection {
+                Some(collection) => format!("no entry `{}` in {}", id, collection.name),
+                None => format!("no entry `{}`", id),
+            });
+        };
+        if let Some(other) = matches.next() {
+            return Err(format!(
+                "`{}` is both a {} and a {}; prefix it as `{}:{}`",
+                id,
+                target.collection.singular,
+                other.collection.singular,
+                target.collection.singular,
+                id
+            ));
+        }
+        Ok(target)
+    }
+}
+
+/// Splits `project:id` into the collection of `collections` it names and the id.
+fn parse<'a>(
+    collections: &'static [Collection],
+    reference: &'a str,
+) -> Result<(Option<&'static Collection>, &'a str), String> {
+    let reference = reference.trim();
+    let (collection, id) = match reference.split_once(':') {
+        Some((prefix, id)) => {
+            let prefix = prefix.trim();
+            let collection = collections
+                .iter()
+                .find(|collection| {
+                    collection.singular.eq_ignore_ascii_case(prefix)
+                        || collection.name.eq_ignore_ascii_case(prefix)
+                })
+                .ok_or_else(|| format!("unknown collection `{}`", prefix))?;
+            (Some(collection), id.trim())
+        }
+        None => (None, reference),
+    };
+    if id.is_empty() {
+        return Err("missing entry id".to_string());
+    }
+    Ok((collection, id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collection::TEST_COLLECTIONS;
+
+    fn collection(name: &str) -> &'static Collection {
+        Collection::by_name(TEST_COLLECTIONS, name).unwrap()
+    }
+
+    fn targets() -> LinkTargets {
+        let mut targets = LinkTargets::new(TEST_COLLECTIONS);
+        targets.push(collection("posts"), "hello", "Hello, World");
+        targets.push(collection("projects"), "blog", "The Blog");
+        targets.push(collection("posts"), "blog", "Starting a Blog");
+        targets
+    }
+
+    #[test]
+    fn resolves_ids_across_collections() {
+        let targets = targets();
+        let hello = targets.resolve("hello").unwrap();
+        assert_eq!(hello.title, "Hello, World");
+        assert_eq!(hello.path(), "/blog/hello");
+        assert_eq!(targets.resolve("post:hello").unwrap(), hello);
+        assert_eq!(targets.resolve(" Posts : hello ").unwrap(), hello);
+        assert_eq!(
+            targets.resolve("project:blog").unwrap().path(),
+            "/projects/blog"
+        );
+    }
+
+    #[test]
+    fn reports_unknown_and_ambiguous_ids() {
+        let targets = targets();
+        assert_eq!(
+            targets.resolve("missing").unwrap_err(),
+            "no entry `missing`"
+        );
+        assert_eq!(
+            targets.resolve("project:hello").unwrap_err(),
+            "no entry `hello` in projects"
+        );
+        assert!(targets
+            .resolve("talk:hello")
+            .unwrap_err()
+            .contains("`talk`"));
+        assert!(targets.resolve("post:").is_err());
+        assert!(targets.resolve("blog").unwrap_err().contains("prefix it"));
+    }
+}
@@ -0,0 +1,322 @@
+//! GitHub-style alerts and collapsible callouts, shared by the app and the build script.
+//!
+//! A blockquote whose first line is `[!NOTE]`, `[!TIP]`, `[!IMPORTANT]`,
+//! `[!WARNING]` or `[!CAUTION]` becomes a callout. Text after the marker
+//! replaces the default title, and a `-` or `+` right after it makes the
+//! callout a `<details>` block that starts closed or open:
+//!
+//! ```markdown
+//! > [!WARNING]- Breaking changes in 0.7
+//! > Shown once the reader expands the callout.
+//! ```
+
+use pulldown_cmark::{html, CowStr, Event, Tag, TagEnd};
+
+/// The alert types GitHub recognizes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CalloutKind {
+    Note,
+    Tip,
+    Important,
+    Warning,
+    Caution,
+}
+
+impl CalloutKind {
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "note" => Some(Self::Note),
+            "tip" => Some(Self::Tip),
+            "important" => Some(Self::Important),
+            "warning" => Some(Self::Warning),
+            "caution" => Some(Self::Caution),
+            _ => None,
+        }
+    }
+
+    fn class(self) -> &'static str {
+        match self {
+            Self::Note => "callout-note",
+            Self::Tip => "callout-tip",
+            Self::Important => "callout-important",
+            Self::Warning => "callout-warning",
+            Self::Caution => "callout-caution",
+        }
+    }
+
+    /// Default title, used when the marker line has no text of its own.
+    fn label(self) -> &'static str {
+        match self {
+            Self::Note => "Note",
+            Self::Tip => "Tip",
+            Self::Important => "Important",
+            Self::Warning => "Warning",
+            Self::Caution => "Caution",
+        }
+    }
+
+    /// Material Symbols ligature shown before the title.
+    fn icon(self) -> &'static str {
+        match self {
+            Self::Note => "info",
+            Self::Tip => "lightbulb",
+            Self::Important => "feedback",
+            Self::Warning => "warning",
+            Self::Caution => "report",
+        }
+    }
+}
+
+/// A callout marker: its kind and, for collapsible callouts, whether it starts open.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Callout {
+    kind: CalloutKind,
+    collapsible: Option<bool>,
+}
+
+impl Callout {
+    fn open_html(self, title: &str) -> String {
+        let icon = format!(
+            r#"<span class="material-symbols-outlined callout-icon" aria-hidden="true">{}</span>"#,
+            self.kind.icon()
+        );
+        match self.collapsible {
+            Some(open) => format!(
+                r#"<details class="callout {}"{}><summary class="callout-title">{}<span>{}</span><span class="material-symbols-outlined callout-chevron" aria-hidden="true">expand_more</span></summary><div class="callout-body">"#,
+                self.kind.class(),
+                if open { " open" } else { "" },
+                icon,
+                title
+            ),
+            None => format!(
+                r#"<aside class="callout {}" role="note"><p class="callout-title">{}<span>{}</span></p><div class="callout-body">"#,
+                self.kind.class(),
+                icon,
+                title
+            ),
+        }
+    }
+
+    fn close_html(self) -> &'static str {
+        match self.collapsible {
+            Some(_) => "</div></details>",
+            None => "</div></aside>",
+        }
+    }
+}
+
+/// Reads `[!KIND]`, an optional `-`/`+`, and the custom title that follows.
+fn parse_marker(text: &str) -> Option<(Callout, &str)> {
+    let rest = text.strip_prefix("[!")?;
+    let (name, rest) = rest.split_once(']')?;
+    let kind = CalloutKind::parse(name)?;
+    let (collapsible, title) = match rest.as_bytes().first() {
+        Some(b'-') => (Some(false), &rest[1..]),
+        Some(b'+') => (Some(true), &rest[1..]),
+        _ => (None, rest),
+    };
+    Some((Callout { kind, collapsible }, title.trim_start()))
+}
+
+/// Where the first line of the latest blockquote has been read up to.
+enum State<'a> {
+    /// Not at the start of a blockquote.
+    Idle,
+    /// The held-back `Start(BlockQuote)` and `Start(Paragraph)`, then the text seen so far.
+    Marker(Vec<Event<'a>>, String),
+    /// A callout was found; its title events are collected up to the end of the line.
+    Title(Callout, Vec<Event<'a>>),
+}
+
+/// Rewrites callout blockquotes in a pulldown-cmark event stream; see the module docs.
+pub struct Callouts<'a> {
+    state: State<'a>,
+    /// For each open blockquote, the callout it was turned into, if any.
+    open: Vec<Option<Callout>>,
+}
+
+impl Default for Callouts<'_> {
+    fn default() -> Self {
+        Self {
+            state: State::Idle,
+            open: Vec::new(),
+        }
+    }
+}
+
+impl<'a> Callouts<'a> {
+    /// Takes the next event and returns the events to emit in its place.
+    pub fn process(&mut self, event: Event<'a>) -> Vec<Event<'a>> {
+        match std::mem::replace(&mut self.state, State::Idle) {
+            State::Idle => self.process_idle(event),
+            State::Marker(mut held, mut text) => match event {
+                Event::Start(Tag::Paragraph) if held.len() == 1 => {
+                    held.push(event);
+                    self.state = State::Marker(held, text);
+                    Vec::new()
+                }
+                Event::Text(ref chunk) if held.len() > 1 => {
+                    text.push_str(chunk);
+                    held.push(event);
+                    self.state = State::Marker(held, text);
+                    Vec::new()
+                }
+                // The marker's line of text ended; decide whether this is a callout
+                event => match parse_marker(&text) {
+                    Some((callout, title)) if held.len() > 1 => {
+                        *self.open.last_mut().unwrap() = Some(callout);
+                        let title = if title.is_empty() {
+                            Vec::new()
+                        } else {
+                            vec![Event::Text(CowStr::from(title.to_string()))]
+                        };
+                        self.state = State::Title(callout, title);
+                        self.process(event)
+                    }
+                    // Not a callout: let the blockquote through untouched
+                    _ => {
+                        let mut events = held;
+                        events.extend(self.process_idle(event));
+                        events
+                    }
+                },
+            },
+            State::Title(callout, mut title) => match event {
+                Event::SoftBreak | Event::HardBreak | Event::End(TagEnd::Paragraph) => {
+                    let title_html = if title.is_empty() {
+                        callout.kind.label().to_string()
+                    } else {
+                        let mut html = String::new();
+                        html::push_html(&mut html, title.into_iter());
+                        html
+                    };
+                    let mut events = vec![Event::Html(callout.open_html(&title_html).into())];
+                    // The body's first paragraph continues on the next line
+                    if event != Event::End(TagEnd::Paragraph) {
+                        events.push(Event::Start(Tag::Paragraph));
+                    }
+                    events
+                }
+                event => {
+                    title.push(event);
+                    self.state = State::Title(callout, title);
+                    Vec::new()
+                }
+            },
+        }
+    }
+
+    fn process_idle(&mut self, event: Event<'a>) -> Vec<Event<'a>> {
+        match event {
+            Event::Start(Tag::BlockQuote(_)) => {
+                self.open.push(None);
+                self.state = State::Marker(vec![event], String::new());
+                Vec::new()
+            }
+            Event::End(TagEnd::BlockQuote(_)) => match self.open.pop().flatten() {
+                Some(callout) => vec![Event::Html(callout.close_html().into())],
+                None => vec![event],
+            },
+            event => vec![event],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::markdown::options;
+    use pulldown_cmark::Parser;
+
+    fn render(markdown: &str) -> String {
+        let mut callouts = Callouts::default();
+        let events = Parser::new_ext(markdown, options()).flat_map(|event| callouts.process(event));
+        let mut out = String::new();
+        html::push_html(&mut out, events);
+        out
+    }
+
+    /// The icon and title markup inside a callout's title element.
+    fn title(icon: &str, text: &str) -> String {
+        format!(
+            r#"<span class="material-symbols-outlined callout-icon" aria-hidden="true">{}</span><span>{}</span>"#,
+            icon, text
+        )
+    }
+
+    #[test]
+    fn turns_each_alert_kind_into_a_callout() {
+        for (marker, class, icon, label) in [
+            ("NOTE", "callout-note", "info", "Note"),
+            ("TIP", "callout-tip", "lightbulb", "Tip"),
+            ("IMPORTANT", "callout-important", "feedback", "Important"),
+            ("WARNING", "callout-warning", "warning", "Warning"),
+            ("CAUTION", "callout-caution", "report", "Caution"),
+        ] {
+            assert_eq!(
+                render(&format!("> [!{}]\n> Body", marker)),
+                format!(
+                    "<aside class=\"callout {}\" role=\"note\"><p class=\"callout-title\">{}</p><div class=\"callout-body\">\n<p>Body</p>\n</div></aside>",
+                    class,
+                    title(icon, label)
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn uses_the_text_after_the_marker_as_title() {
+        let html = render("> [!tip] Custom *title*\n> Body\n> more");
+        assert!(
+            html.contains(&title("lightbulb", "Custom <em>title</em>")),
+            "{}",
+            html
+        );
+        assert!(html.contains("<p>Body\nmore</p>"));
+    }
+
+    #[test]
+    fn makes_collapsible_callouts_details() {
+        let closed = render("> [!WARNING]- Closed\n> Body");
+        assert!(
+            closed.starts_with(
+                r#"<details class="callout callout-warning"><summary class="callout-title">"#
+            ),
+            "{}",
+            closed
+        );
+        assert!(closed.contains(&title("warning", "Closed")));
+        assert!(closed.ends_with("<p>Body</p>\n</div></details>"));
+
+        let open = render("> [!CAUTION]+\n> Body");
+        assert!(
+            open.starts_with(
+                r#"<details class="callout callout-caution" open><summary class="callout-title">"#
+            ),
+            "{}",
+            open
+        );
+        assert!(open.contains(&title("report", "Caution")));
+    }
+
+    #[test]
+    fn leaves_other_blockquotes_untouched() {
+        for markdown in [
+            "> Just a quote\n> [!NOTE]",
+            "> [!NOPE]\n> Body",
+            "> *Emphasis* first\n\n> Second",
+        ] {
+            let mut plain = String::new();
+            html::push_html(&mut plain, Parser::new_ext(markdown, options()));
+            assert_eq!(render(markdown), plain);
+        }
+    }
+
+    #[test]
+    fn keeps_blockquotes_nested_in_a_callout() {
+        let html = render("> [!NOTE]\n> > inner\n\nafter");
+        assert!(html.contains(
+            "<div class=\"callout-body\">\n<blockquote>\n<p>inner</p>\n</blockquote>\n</div></aside>\n<p>after</p>"
+        ));
+    }
+}
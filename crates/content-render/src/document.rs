@@ -0,0 +1,458 @@
+//! Builds the [`Node`] tree of a rendered document from pulldown-cmark events,
+//! shared by the app and the build script.
+//!
+//! Markdown tags map to the same elements `pulldown_cmark::html` would write.
+//! Raw HTML, whether written in the markdown or produced by an earlier stage
+//! (highlighted code, MathML, callouts, pictures), is tokenized into the same
+//! tree, so a `<details>` opened in one HTML block and closed in another still
+//! ends up as one element around the markdown between them.
+
+use content_model::html::{Node, TreeBuilder};
+use pulldown_cmark::{Alignment, BlockQuoteKind, CodeBlockKind, Event, LinkType, Tag, TagEnd};
+use std::collections::HashMap;
+
+/// Turns a (rewritten) event stream into a document tree. Footnote references
+/// link to their definition on `page_url`, like other links to a section of
+/// the page, since a bare `#name` would resolve against the `<base>` tag.
+pub fn build_tree<'a>(events: impl Iterator<Item = Event<'a>>, page_url: &str) -> Vec<Node> {
+    let mut document = DocumentBuilder {
+        page_url: page_url.to_string(),
+        ..Default::default()
+    };
+    for event in events {
+        document.event(event);
+    }
+    document.tree.finish()
+}
+
+/// Markdown state kept while the tree is assembled.
+#[derive(Default)]
+struct DocumentBuilder {
+    tree: TreeBuilder,
+    /// Alt text of the image being read, with its `src` and `title`.
+    image: Option<(String, String, String)>,
+    table_alignments: Vec<Alignment>,
+    table_cell: usize,
+    in_table_head: bool,
+    /// Footnote numbers in order of first use, as the HTML renderer assigns them.
+    footnotes: HashMap<String, usize>,
+    page_url: String,
+}
+
+impl DocumentBuilder {
+    fn event(&mut self, event: Event) {
+        if let Some((alt, _, _)) = self.image.as_mut() {
+            match event {
+                Event::End(TagEnd::Image) => {
+                    let (alt, src, title) = self.image.take().unwrap_or_default();
+                    let mut attrs = vec![attr("src", src), attr("alt", alt)];
+                    if !title.is_empty() {
+                        attrs.push(attr("title", title));
+                    }
+                    self.tree.void("img", attrs);
+                }
+                Event::Text(text) | Event::Code(text) => alt.push_str(&text),
+                _ => {}
+            }
+            return;
+        }
+
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => self.tree.text(&text),
+            Event::Code(code) => {
+                self.tree.open("code", Vec::new());
+                self.tree.text(&code);
+                self.tree.close("code");
+            }
+            Event::InlineMath(latex) | Event::DisplayMath(latex) => self.tree.text(&latex),
+            Event::Html(html) | Event::InlineHtml(html) => self.tree.html(&html),
+            Event::FootnoteReference(name) => {
+                let number = self.footnote_number(&name);
+                self.tree
+                    .open("sup", vec![attr("class", "footnote-reference")]);
+                self.tree.open(
+                    "a",
+                    vec![attr("href", format!("{}#{}", self.page_url, name))],
+                );
+                self.tree.text(&number.to_string());
+                self.tree.close("sup");
+            }
+            Event::SoftBreak => self.tree.text("\n"),
+            Event::HardBreak => self.tree.void("br", Vec::new()),
+            Event::Rule => self.tree.void("hr", Vec::new()),
+            Event::TaskListMarker(checked) => {
+                let mut attrs = vec![attr("type", "checkbox"), attr("disabled", "")];
+                if checked {
+                    attrs.push(attr("checked", ""));
+                }
+                self.tree.void("input", attrs);
+            }
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph => self.tree.open("p", Vec::new()),
+            Tag::Heading {
+                level,
+                id,
+                classes,
+                attrs,
+            } => {
+                let mut all = Vec::new();
+                if let Some(id) = id {
+                    all.push(attr("id", id.to_string()));
+                }
+                if !classes.is_empty() {
+                    let classes: Vec<&str> = classes.iter().map(|class| class.as_ref()).collect();
+                    all.push(attr("class", classes.join(" ")));
+                }
+                for (name, value) in attrs {
+                    all.push(attr(
+                        name.to_string(),
+                        value.map(|v| v.to_string()).unwrap_or_default(),
+                    ));
+                }
+                self.tree.open(&level.to_string(), all);
+            }
+            Tag::BlockQuote(kind) => {
+                let attrs = match kind {
+                    Some(kind) => vec![attr("class", blockquote_class(kind))],
+                    None => Vec::new(),
+                };
+                self.tree.open("blockquote", attrs);
+            }
+            Tag::CodeBlock(kind) => {
+                self.tree.open("pre", Vec::new());
+                let lang = match kind {
+                    CodeBlockKind::Fenced(info) => {
+                        info.split(' ').next().unwrap_or_default().to_string()
+                    }
+                    CodeBlockKind::Indented => String::new(),
+                };
+                let attrs = if lang.is_empty() {
+                    Vec::new()
+                } else {
+                    vec![attr("class", format!("language-{}", lang))]
+                };
+                self.tree.open("code", attrs);
+            }
+            Tag::HtmlBlock | Tag::MetadataBlock(_) => {}
+            Tag::List(Some(1)) => self.tree.open("ol", Vec::new()),
+            Tag::List(Some(start)) => self.tree.open("ol", vec![attr("start", start.to_string())]),
+            Tag::List(None) => self.tree.open("ul", Vec::new()),
+            Tag::Item => self.tree.open("li", Vec::new()),
+            Tag::FootnoteDefinition(name) => {
+                let number = self.footnote_number(&name);
+                self.tree.open(
+                    "div",
+                    vec![
+                        attr("class", "footnote-definition"),
+                        attr("id", name.to_string()),
+                    ],
+                );
+                self.tree
+                    .open("sup", vec![attr("class", "footnote-definition-label")]);
+                self.tree.text(&number.to_string());
+                self.tree.close("sup");
+            }
+            Tag::DefinitionList => self.tree.open("dl", Vec::new()),
+            Tag::DefinitionListTitle => self.tree.open("dt", Vec::new()),
+            Tag::DefinitionListDefinition => self.tree.open("dd", Vec::new()),
+            Tag::Table(alignments) => {
+                self.table_alignments = alignments;
+                self.tree.open("table", Vec::new());
+            }
+            Tag::TableHead => {
+                self.in_table_head = true;
+                self.table_cell = 0;
+                self.tree.open("thead", Vec::new());
+                self.tree.open("tr", Vec::new());
+            }
+            Tag::TableRow => {
+                self.table_cell = 0;
+                self.tree.open("tr", Vec::new());
+            }
+            Tag::TableCell => {
+                let tag = if self.in_table_head { "th" } else { "td" };
+                let attrs = match self.table_alignments.get(self.table_cell) {
+                    Some(Alignment::Left) => vec![attr("style", "text-align: left")],
+                    Some(Alignment::Center) => vec![attr("style", "text-align: center")],
+                    Some(Alignment::Right) => vec![attr("style", "text-align: right")],
+                    _ => Vec::new(),
+                };
+                self.tree.open(tag, attrs);
+            }
+            Tag::Emphasis => self.tree.open("em", Vec::new()),
+            Tag::Strong => self.tree.open("strong", Vec::new()),
+            Tag::Strikethrough => self.tree.open("del", Vec::new()),
+            Tag::Superscript => self.tree.open("sup", Vec::new()),
+            Tag::Subscript => self.tree.open("sub", Vec::new()),
+            Tag::Link {
+                link_type,
+                dest_url,
+                title,
+                ..
+            } => {
+                let href = match link_type {
+                    LinkType::Email => format!("mailto:{}", dest_url),
+                    _ => dest_url.to_string(),
+                };
+                let mut attrs = vec![attr("href", href)];
+                if !title.is_empty() {
+                    attrs.push(attr("title", title.to_string()));
+                }
+                self.tree.open("a", attrs);
+            }
+            Tag::Image {
+                dest_url, title, ..
+            } => {
+                self.image = Some((String::new(), dest_url.to_string(), title.to_string()));
+            }
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        let name = match tag {
+            TagEnd::Paragraph => "p",
+            TagEnd::Heading(level) => return self.tree.close(&level.to_string()),
+            TagEnd::BlockQuote(_) => "blockquote",
+            TagEnd::CodeBlock => "pre",
+            TagEnd::HtmlBlock | TagEnd::MetadataBlock(_) | TagEnd::Image => return,
+            TagEnd::List(true) => "ol",
+            TagEnd::List(false) => "ul",
+            TagEnd::Item => "li",
+            TagEnd::FootnoteDefinition => "div",
+            TagEnd::DefinitionList => "dl",
+            TagEnd::DefinitionListTitle => "dt",
+            TagEnd::DefinitionListDefinition => "dd",
+            TagEnd::Table => "table",
+            TagEnd::TableHead => {
+                self.in_table_head = false;
+                self.tree.close("thead");
+                return self.tree.open("tbody", Vec::new());
+            }
+            TagEnd::TableRow => "tr",
+            TagEnd::TableCell => {
+                self.table_cell += 1;
+                if self.in_table_head {
+                    "th"
+                } else {
+                    "td"
+                }
+            }
+            TagEnd::Emphasis => "em",
+            TagEnd::Strong => "strong",
+            TagEnd::Strikethrough => "del",
+            TagEnd::Superscript => "sup",
+            TagEnd::Subscript => "sub",
+            TagEnd::Link => "a",
+        };
+        self.tree.close(name);
+    }
+
+    fn footnote_number(&mut self, name: &str) -> usize {
+        let next = self.footnotes.len() + 1;
+        *self.footnotes.entry(name.to_string()).or_insert(next)
+    }
+}
+
+fn attr(name: impl Into<String>, value: impl Into<String>) -> (String, String) {
+    (name.into(), value.into())
+}
+
+fn blockquote_class(kind: BlockQuoteKind) -> &'static str {
+    match kind {
+        BlockQuoteKind::Note => "markdown-alert-note",
+        BlockQuoteKind::Tip => "markdown-alert-tip",
+        BlockQuoteKind::Important => "markdown-alert-important",
+        BlockQuoteKind::Warning => "markdown-alert-warning",
+        BlockQuoteKind::Caution => "markdown-alert-caution",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::markdown::options;
+    use pulldown_cmark::Parser;
+
+    fn tree(markdown: &str) -> Vec<Node> {
+        build_tree(Parser::new_ext(markdown, options()), "/blog/post")
+    }
+
+    fn element(tag: &str, attrs: &[(&str, &str)], children: Vec<Node>) -> Node {
+        Node::Element {
+            tag: tag.to_string(),
+            attrs: attrs
+                .iter()
+                .map(|&(name, value)| attr(name, value))
+                .collect(),
+            children,
+        }
+    }
+
+    fn text(text: &str) -> Node {
+        Node::Text(text.to_string())
+    }
+
+    #[test]
+    fn links_footnotes_through_the_page_url() {
+        assert_eq!(
+            tree("Claim[^note] and more[^1].\n\n[^1]: First.\n[^note]: Source."),
+            vec![
+                element(
+                    "p",
+                    &[],
+                    vec![
+                        text("Claim"),
+                        element(
+                            "sup",
+                            &[("class", "footnote-reference")],
+                            vec![element(
+                                "a",
+                                &[("href", "/blog/post#note")],
+                                vec![text("1")]
+                            )]
+                        ),
+                        text(" and more"),
+                        element(
+                            "sup",
+                            &[("class", "footnote-reference")],
+                            vec![element("a", &[("href", "/blog/post#1")], vec![text("2")])]
+                        ),
+                        text("."),
+                    ]
+                ),
+                element(
+                    "div",
+                    &[("class", "footnote-definition"), ("id", "1")],
+                    vec![
+                        element(
+                            "sup",
+                            &[("class", "footnote-definition-label")],
+                            vec![text("2")]
+                        ),
+                        element("p", &[], vec![text("First.")]),
+                    ]
+                ),
+                element(
+                    "div",
+                    &[("class", "footnote-definition"), ("id", "note")],
+                    vec![
+                        element(
+                            "sup",
+                            &[("class", "footnote-definition-label")],
+                            vec![text("1")]
+                        ),
+                        element("p", &[], vec![text("Source.")]),
+                    ]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn builds_tables_with_alignment() {
+        let left = [("style", "text-align: left")];
+        let right = [("style", "text-align: right")];
+        assert_eq!(
+            tree("| Name | Size | Note |\n|:--|--:|---|\n| a | 1 | x |\n| b | 2 | |"),
+            vec![element(
+                "table",
+                &[],
+                vec![
+                    element(
+                        "thead",
+                        &[],
+                        vec![element(
+                            "tr",
+                            &[],
+                            vec![
+                                element("th", &left, vec![text("Name")]),
+                                element("th", &right, vec![text("Size")]),
+                                element("th", &[], vec![text("Note")]),
+                            ]
+                        )]
+                    ),
+                    element(
+                        "tbody",
+                        &[],
+                        vec![
+                            element(
+                                "tr",
+                                &[],
+                                vec![
+                                    element("td", &left, vec![text("a")]),
+                                    element("td", &right, vec![text("1")]),
+                                    element("td", &[], vec![text("x")]),
+                                ]
+                            ),
+                            element(
+                                "tr",
+                                &[],
+                                vec![
+                                    element("td", &left, vec![text("b")]),
+                                    element("td", &right, vec![text("2")]),
+                                    element("td", &[], vec![]),
+                                ]
+                            ),
+                        ]
+                    ),
+                ]
+            )]
+        );
+    }
+
+    #[test]
+    fn builds_task_lists() {
+        let checkbox = |checked: bool| {
+            let mut attrs = vec![("type", "checkbox"), ("disabled", "")];
+            if checked {
+                attrs.push(("checked", ""));
+            }
+            element("input", &attrs, vec![])
+        };
+        assert_eq!(
+            tree("- [x] Done\n- [ ] *Todo*"),
+            vec![element(
+                "ul",
+                &[],
+                vec![
+                    element("li", &[], vec![checkbox(true), text("Done")]),
+                    element(
+                        "li",
+                        &[],
+                        vec![checkbox(false), element("em", &[], vec![text("Todo")])]
+                    ),
+                ]
+            )]
+        );
+    }
+
+    #[test]
+    fn builds_images_with_plain_alt_text() {
+        assert_eq!(
+            tree("![A *bold* `move`](cover.png \"Cover\") ![](plain.png)"),
+            vec![element(
+                "p",
+                &[],
+                vec![
+                    element(
+                        "img",
+                        &[
+                            ("src", "cover.png"),
+                            ("alt", "A bold move"),
+                            ("title", "Cover")
+                        ],
+                        vec![]
+                    ),
+                    text(" "),
+                    element("img", &[("src", "plain.png"), ("alt", "")], vec![]),
+                ]
+            )]
+        );
+    }
+}
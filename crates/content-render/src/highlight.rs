@@ -0,0 +1,49 @@
+//! Syntax highlighting for fenced code blocks, shared by the app and the build script.
+//!
+//! Code is tokenized with syntect's bundled Sublime Text grammars and wrapped in
+//! spans classed by scope (`hl-keyword`, `hl-string`, `hl-comment`, ...). The
+//! colors live in `tailwind.css`, with a light and a dark palette, so blocks
+//! follow the theme toggle without being re-rendered.
+
+use content_model::html::escape;
+use std::sync::OnceLock;
+use syntect::html::{ClassStyle, ClassedHTMLGenerator};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+
+/// Scope classes are prefixed so grammar names such as `table` cannot clash with Tailwind.
+const CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: "hl-" };
+
+fn syntaxes() -> &'static SyntaxSet {
+    static SYNTAXES: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAXES.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+/// Renders a code block as `<pre><code>`, highlighted when `lang` names a known
+/// grammar by name or file extension (`rust`, `rs`, `yaml`, `sh`, ...).
+/// Anything else is emitted as plain escaped text.
+pub fn highlight_code_block(code: &str, lang: &str) -> String {
+    let highlighted = (!lang.is_empty())
+        .then(|| syntaxes().find_syntax_by_token(lang))
+        .flatten()
+        .and_then(|syntax| {
+            let mut generator =
+                ClassedHTMLGenerator::new_with_class_style(syntax, syntaxes(), CLASS_STYLE);
+            for line in LinesWithEndings::from(code) {
+                generator
+                    .parse_html_for_line_which_includes_newline(line)
+                    .ok()?;
+            }
+            Some(generator.finalize())
+        });
+    let class = if lang.is_empty() {
+        String::new()
+    } else {
+        format!(r#" class="language-{}""#, escape(lang))
+    };
+    format!(
+        "<pre><code{}>{}</code></pre>\n",
+        class,
+        highlighted.unwrap_or_else(|| escape(code))
+    )
+}
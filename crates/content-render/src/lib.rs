@@ -0,0 +1,12 @@
+//! Renders entry markdown to the document tree the app displays.
+//!
+//! The build script pre-renders every entry with it, and the app renders
+//! `index.md` itself with the same code when built with `runtime-markdown`, so
+//! both produce exactly the same tree. Site settings such as the entry's
+//! collection, the base path and the sanitizer policy are passed in by the caller.
+
+pub mod callout;
+pub mod document;
+pub mod highlight;
+pub mod markdown;
+pub mod math;
@@ -0,0 +1,454 @@
+//! Markdown rendering shared by the app and the build script, so pages and
+//! feeds are rendered with exactly the same rules.
+
+use crate::callout::Callouts;
+use crate::document::build_tree;
+use crate::highlight::highlight_code_block;
+use crate::math::render_math;
+use content_model::collection::Collection;
+use content_model::html::{escape, parse_fragment, to_html, Node};
+use content_model::path::{entry_file, is_external};
+use content_model::rendered::TocEntry;
+use content_model::responsive::{ResponsiveImage, ARTICLE_IMAGE_SIZES, IMAGE_FORMATS};
+use content_model::sanitize::{sanitize, Policy};
+use content_model::shortcode::{is_shortcode, marker};
+use content_model::slug::Slugger;
+use content_model::wikilink::LinkTargets;
+use pulldown_cmark::{CodeBlockKind, Event, LinkType, Options, Parser, Tag, TagEnd};
+use std::collections::HashMap;
+
+pub fn options() -> Options {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_FOOTNOTES);
+    options.insert(Options::ENABLE_TASKLISTS);
+    options.insert(Options::ENABLE_MATH);
+    options.insert(Options::ENABLE_WIKILINKS);
+    options
+}
+
+/// Renders a visitor's comment to HTML, keeping only what `policy` allows.
+/// Comments get plain CommonMark with strikethrough: no shortcodes, math or
+/// rewritten links.
+pub fn render_comment(markdown: &str, policy: &Policy) -> String {
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(
+        &mut html,
+        Parser::new_ext(markdown, Options::ENABLE_STRIKETHROUGH),
+    );
+    to_html(&sanitize(parse_fragment(&html), policy))
+}
+
+/// Renders markdown to HTML, rewriting links and images so they resolve
+/// under `base_path` (e.g. `""`, `/repo_name` or `https://user.github.io/repo_name`).
+pub fn render_markdown(
+    markdown: &str,
+    post_id: &str,
+    collection: &Collection,
+    base_path: &str,
+    targets: &LinkTargets,
+    policy: &Policy,
+) -> String {
+    to_html(&render_document(
+        markdown,
+        post_id,
+        collection,
+        base_path,
+        &HashMap::new(),
+        targets,
+        policy,
+    ))
+}
+
+/// Renders the markdown of entry `post_id` in `collection` to the document
+/// tree the app displays, sanitized with `policy`. Images found in `images` (keyed by their path inside
+/// the entry folder) become a `<picture>` with AVIF/WebP `srcset`s, explicit
+/// dimensions and lazy loading.
+///
+/// `[[id]]` wiki links resolve against `targets`, showing a marked-up
+/// placeholder when they name no entry. Headings get the ids listed by
+/// [`extract_toc`] and end with a link to themselves on the entry's page;
+/// code blocks are syntax highlighted, `$...$` / `$$...$$` math becomes
+/// MathML, `> [!NOTE]` blockquotes become callouts and shortcodes leave a
+/// marker (see [`content_model::shortcode`]).
+pub fn render_document(
+    markdown: &str,
+    post_id: &str,
+    collection: &Collection,
+    base_path: &str,
+    images: &HashMap<String, ResponsiveImage>,
+    targets: &LinkTargets,
+    policy: &Policy,
+) -> Vec<Node> {
+    let content_type = collection.name;
+    let page_url = format!("{}/{}/{}", base_path, collection.route, post_id);
+    // Point `[[id]]` links at their entry, labelled with its title unless a `|label` is given
+    let mut wiki_link: Option<WikiLinkState> = None;
+    let parser = Parser::new_ext(markdown, options()).flat_map(|event| match event {
+        Event::Start(Tag::Link {
+            link_type: LinkType::WikiLink { has_pothole },
+            dest_url,
+            title,
+            id,
+        }) => match targets.resolve(&dest_url) {
+            Ok(target) => {
+                let mut events = vec![Event::Start(Tag::Link {
+                    link_type: LinkType::Inline,
+                    dest_url: target.path().into(),
+                    title,
+                    id,
+                })];
+                if !has_pothole {
+                    events.push(Event::Text(target.title.clone().into()));
+                }
+                wiki_link = Some(WikiLinkState::Resolved {
+                    keep_label: has_pothole,
+                });
+                events
+            }
+            Err(message) => {
+                wiki_link = Some(WikiLinkState::Broken);
+                vec![Event::InlineHtml(
+                    format!(
+                        r#"<span class="wikilink-broken" title="Broken link: {}">"#,
+                        escape(&message)
+                    )
+                    .into(),
+                )]
+            }
+        },
+        Event::End(TagEnd::Link) => match wiki_link.take() {
+            Some(WikiLinkState::Broken) => vec![Event::InlineHtml("</span>".into())],
+            _ => vec![event],
+        },
+        Event::Text(_) | Event::Code(_)
+            if wiki_link == Some(WikiLinkState::Resolved { keep_label: false }) =>
+        {
+            Vec::new()
+        }
+        event => vec![event],
+    });
+    let parser = parser.map(|event| match event {
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => {
+            let mut new_dest = dest_url.to_string();
+            if new_dest.starts_with('/') {
+                // Case 1: Root-relative path (e.g., "/about")
+                new_dest = format!("{}{}", base_path, new_dest);
+            } else if new_dest.starts_with('#') {
+                // Case 2: A heading on this page (e.g., "#setup"), past the <base> tag
+                new_dest = format!("{}{}", page_url, new_dest);
+            } else if !is_external(&new_dest) {
+                // Case 3: Relative path to the current directory (e.g., "next-post.md")
+                new_dest = format!(
+                    "{}/{}",
+                    base_path,
+                    entry_file(content_type, post_id, &new_dest)
+                );
+            }
+            // Case 4: External URL or other scheme (e.g., "mailto:") - leave as is
+
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url: new_dest.into(),
+                title,
+                id,
+            })
+        }
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => {
+            let mut new_dest = dest_url.to_string();
+
+            if new_dest.starts_with('/') {
+                // Case 1: Root-relative path (e.g., "/assets/logo.png")
+                new_dest = format!("{}{}", base_path, new_dest);
+            } else if !is_external(&new_dest) {
+                // Case 2: Relative path to the post/project folder (e.g., "thumbnail.png")
+                new_dest = format!(
+                    "{}/{}",
+                    base_path,
+                    entry_file(content_type, post_id, &new_dest)
+                );
+            }
+            // Case 3: External URL (any scheme) - leave as is
+
+            Event::Start(Tag::Image {
+                link_type,
+                dest_url: new_dest.into(),
+                title,
+                id,
+            })
+        }
+        _ => event,
+    });
+
+    // Give headings the table of contents ids and a trailing "copy link" anchor
+    let mut heading_ids = extract_toc(markdown).into_iter().map(|entry| entry.id);
+    let mut current_heading = None;
+    let parser = parser.flat_map(|event| match event {
+        Event::Start(Tag::Heading {
+            level,
+            id: _,
+            classes,
+            attrs,
+        }) => {
+            current_heading = heading_ids.next();
+            vec![Event::Start(Tag::Heading {
+                level,
+                id: current_heading.clone().map(Into::into),
+                classes,
+                attrs,
+            })]
+        }
+        Event::End(TagEnd::Heading(level)) => match current_heading.take() {
+            Some(id) => vec![
+                Event::InlineHtml(heading_anchor(&page_url, &id).into()),
+                Event::End(TagEnd::Heading(level)),
+            ],
+            None => vec![Event::End(TagEnd::Heading(level))],
+        },
+        event => vec![event],
+    });
+
+    // A top-level paragraph holding only `{{< name ... >}}` leaves a marker for the app to mount a component at
+    let mut depth = 0usize;
+    let mut paragraph: Option<Vec<Event>> = None;
+    let parser = parser.flat_map(|event| {
+        let top_level = depth == 0;
+        match event {
+            Event::Start(_) => depth += 1,
+            Event::End(_) => depth -= 1,
+            _ => {}
+        }
+        let Some(mut held) = paragraph.take() else {
+            if top_level && event == Event::Start(Tag::Paragraph) {
+                paragraph = Some(vec![event]);
+                return Vec::new();
+            }
+            return vec![event];
+        };
+        match event {
+            Event::Text(_) | Event::SoftBreak => {
+                held.push(event);
+                paragraph = Some(held);
+                Vec::new()
+            }
+            Event::End(TagEnd::Paragraph) => {
+                let text: String = held
+                    .iter()
+                    .filter_map(|event| match event {
+                        Event::Text(text) => Some(text.as_ref()),
+                        _ => None,
+                    })
+                    .collect();
+                if is_shortcode(&text) {
+                    vec![Event::Html(format!("{}\n", marker(&text)).into())]
+                } else {
+                    held.push(event);
+                    held
+                }
+            }
+            event => {
+                held.push(event);
+                held
+            }
+        }
+    });
+
+    // Turn `> [!NOTE]` style blockquotes into callouts
+    let mut callouts = Callouts::default();
+    let parser = parser.flat_map(|event| callouts.process(event));
+
+    // Collect each code block's text and replace the block with highlighted markup
+    let mut code_block: Option<(String, String)> = None;
+    let parser = parser.filter_map(|event| match event {
+        Event::Start(Tag::CodeBlock(kind)) => {
+            let lang = match kind {
+                CodeBlockKind::Fenced(info) => info
+                    .split([' ', ',', '{'])
+                    .next()
+                    .unwrap_or_default()
+                    .to_string(),
+                CodeBlockKind::Indented => String::new(),
+            };
+            code_block = Some((lang, String::new()));
+            None
+        }
+        Event::Text(text) if code_block.is_some() => {
+            code_block.as_mut()?.1.push_str(&text);
+            None
+        }
+        Event::End(TagEnd::CodeBlock) => {
+            let (lang, code) = code_block.take()?;
+            Some(Event::Html(highlight_code_block(&code, &lang).into()))
+        }
+        Event::InlineMath(latex) => Some(Event::InlineHtml(render_math(&latex, false).into())),
+        Event::DisplayMath(latex) => Some(Event::InlineHtml(render_math(&latex, true).into())),
+        event => Some(event),
+    });
+
+    // Swap images with generated variants for a <picture>, using the alt text collected up to the end tag
+    let entry_prefix = format!("{}/{}", base_path, entry_file(content_type, post_id, ""));
+    let mut picture: Option<(&ResponsiveImage, String, String, String)> = None;
+    let parser = parser.filter_map(|event| {
+        if let Some((_, _, _, alt)) = picture.as_mut() {
+            return match event {
+                Event::End(TagEnd::Image) => {
+                    let (image, src, title, alt) = picture.take()?;
+                    let html = picture_html(image, &src, &alt, &title, base_path);
+                    Some(Event::InlineHtml(html.into()))
+                }
+                Event::Text(text) | Event::Code(text) => {
+                    alt.push_str(&text);
+                    None
+                }
+                _ => None,
+            };
+        }
+        if let Event::Start(Tag::Image {
+            dest_url, title, ..
+        }) = &event
+        {
+            let image = dest_url
+                .strip_prefix(entry_prefix.as_str())
+                .and_then(|path| images.get(path));
+            if let Some(image) = image {
+                picture = Some((
+                    image,
+                    dest_url.to_string(),
+                    title.to_string(),
+                    String::new(),
+                ));
+                return None;
+            }
+        }
+        Some(event)
+    });
+
+    sanitize(build_tree(parser, &page_url), policy)
+}
+
+/// Where the renderer is inside a wiki link.
+#[derive(Clone, Copy, PartialEq)]
+enum WikiLinkState {
+    /// Pointing at an entry; an unpiped link's text is replaced by the entry's title.
+    Resolved { keep_label: bool },
+    /// Naming no entry, so shown as text in a highlighted span.
+    Broken,
+}
+
+/// `<picture>` markup for a body image with generated variants.
+fn picture_html(
+    image: &ResponsiveImage,
+    src: &str,
+    alt: &str,
+    title: &str,
+    base_path: &str,
+) -> String {
+    let sources: String = IMAGE_FORMATS
+        .iter()
+        .map(|(ext, mime)| {
+            format!(
+                r#"<source type="{}" srcset="{}" sizes="{}">"#,
+                mime,
+                escape(&image.srcset(base_path, ext)),
+                ARTICLE_IMAGE_SIZES
+            )
+        })
+        .collect();
+    let title = if title.is_empty() {
+        String::new()
+    } else {
+        format!(r#" title="{}""#, escape(title))
+    };
+    format!(
+        r#"<picture>{}<img src="{}" alt="{}"{} width="{}" height="{}" loading="lazy" decoding="async" style="{}"></picture>"#,
+        sources,
+        escape(src),
+        escape(alt),
+        title,
+        image.width,
+        image.height,
+        escape(&image.placeholder_style())
+    )
+}
+
+/// Link from a heading to itself; the app copies it to the clipboard on click.
+fn heading_anchor(page_url: &str, id: &str) -> String {
+    format!(
+        r#"<a class="heading-anchor" href="{}#{}" aria-label="Copy link to this section"></a>"#,
+        escape(page_url),
+        escape(id)
+    )
+}
+
+/// Collects the document's headings in order for a table of contents, each
+/// with a unique slug of its text as id.
+pub fn extract_toc(markdown: &str) -> Vec<TocEntry> {
+    let mut slugger = Slugger::default();
+    let mut toc = Vec::new();
+    let mut current: Option<TocEntry> = None;
+    for event in Parser::new_ext(markdown, options()) {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                current = Some(TocEntry {
+                    level: level as u8,
+                    text: String::new(),
+                    id: String::new(),
+                });
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some(entry) = current.as_mut() {
+                    entry.text.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some(mut entry) = current.take() {
+                    entry.text = entry.text.trim().to_string();
+                    entry.id = slugger.slug(&entry.text);
+                    toc.push(entry);
+                }
+            }
+            _ => {}
+        }
+    }
+    toc
+}
+
+/// Word count used for the read-time estimate.
+pub fn word_count(markdown: &str) -> usize {
+    markdown.split_whitespace().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use content_model::sanitize::COMMENTS;
+
+    #[test]
+    fn renders_comments_with_the_comment_policy() {
+        assert_eq!(
+            render_comment("Great **post**, ~~not~~ *really*!", &COMMENTS),
+            "<p>Great <strong>post</strong>, <del>not</del> <em>really</em>!</p>"
+        );
+        let html = render_comment(
+            "<script>alert(1)</script>\n\n[x](javascript:alert(1)) ![img](a.png)",
+            &COMMENTS,
+        );
+        assert!(
+            !html.contains("<script") && !html.contains("javascript:"),
+            "{}",
+            html
+        );
+        assert!(!html.contains("<img"), "{}", html);
+    }
+}
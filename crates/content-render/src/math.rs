@@ -0,0 +1,107 @@
+//! LaTeX math rendering, shared by the app and the build script.
+//!
+//! `$...$` and `$$...$$` in markdown are rendered to MathML, which browsers
+//! display natively, so no math JavaScript or web fonts are loaded. LaTeX that
+//! does not parse becomes a visible error box showing the source and the problem.
+
+use content_model::html::escape;
+use pulldown_latex::config::DisplayMode;
+use pulldown_latex::{push_mathml, Parser, RenderConfig, Storage};
+
+/// Renders a LaTeX expression to a `<math>` element, inline or as a display block.
+pub fn render_math(latex: &str, display: bool) -> String {
+    let storage = Storage::new();
+    let events: Vec<_> = Parser::new(latex, &storage).collect();
+    if let Some(Err(error)) = events.iter().find(|event| event.is_err()) {
+        return math_error(latex, &error.to_string(), display);
+    }
+
+    let config = RenderConfig {
+        display_mode: if display {
+            DisplayMode::Block
+        } else {
+            DisplayMode::Inline
+        },
+        // Keeps the source for copy/paste and screen readers that prefer it
+        annotation: Some(latex),
+        ..RenderConfig::default()
+    };
+    let mut mathml = String::new();
+    if let Err(error) = push_mathml(&mut mathml, events.into_iter(), config) {
+        return math_error(latex, &error.to_string(), display);
+    }
+    // Display math sits inside a paragraph, so it is wrapped in a block-styled span
+    if display {
+        format!(r#"<span class="math-display">{}</span>"#, mathml)
+    } else {
+        mathml
+    }
+}
+
+/// A visible box in place of an equation that failed to parse.
+fn math_error(latex: &str, error: &str, display: bool) -> String {
+    let message = error
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches("parsing error: ");
+    let (class, delimiter) = if display {
+        ("math-error math-display", "$$")
+    } else {
+        ("math-error", "$")
+    };
+    format!(
+        r#"<span class="{class}" role="alert" title="Invalid LaTeX: {message}"><code>{delimiter}{source}{delimiter}</code> <small>{message}</small></span>"#,
+        class = class,
+        delimiter = delimiter,
+        source = escape(latex),
+        message = escape(message),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_inline_math_to_mathml() {
+        let html = render_math("x^2", false);
+        assert!(html.starts_with(r#"<math display="inline">"#), "{}", html);
+        assert!(html.ends_with("</math>"));
+        assert!(html.contains("<msup><mi>x</mi><mn>2</mn></msup>"));
+        assert!(html.contains(r#"<annotation encoding="application/x-tex">x^2</annotation>"#));
+    }
+
+    #[test]
+    fn wraps_display_math_in_a_block() {
+        let html = render_math(r"\frac{a}{b}", true);
+        assert!(
+            html.starts_with(r#"<span class="math-display"><math display="block">"#),
+            "{}",
+            html
+        );
+        assert!(html.ends_with("</math></span>"));
+        assert!(html.contains("<mfrac>"));
+    }
+
+    #[test]
+    fn shows_invalid_latex_as_an_escaped_error() {
+        let html = render_math(r"\frac{1", false);
+        assert!(
+            html.starts_with(r#"<span class="math-error" role="alert""#),
+            "{}",
+            html
+        );
+        assert!(html.contains(r"<code>$\frac{1$</code>"));
+        assert!(!html.contains("<math"));
+
+        let html = render_math(r#"\nosuch <b>"quoted"</b>"#, true);
+        assert!(
+            html.starts_with(r#"<span class="math-error math-display" role="alert""#),
+            "{}",
+            html
+        );
+        assert!(html.contains(r"<code>$$\nosuch &lt;b&gt;&quot;quoted&quot;&lt;/b&gt;$$</code>"));
+        assert!(!html.contains("<b>"));
+    }
+}
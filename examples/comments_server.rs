@@ -0,0 +1,179 @@
+//! A local stand-in for the comments API, for trying `COMMENTS_API_URL` out.
+//!
+//! ```sh
+//! cargo run --example comments_server            # http://localhost:8787
+//! cargo run --example comments_server -- 9000    # another port
+//! ```
+//!
+//! Comments are kept in memory, seeded from the `comments.json` files under
+//! `public/content`, and lost when the server stops. Like the build script it
+//! sends every body rendered to `html`, so the app needs no markdown parser. It
+//! answers every origin, so `dx serve` can reach it from another port.
+
+// The site's collections, timezone and comment policy
+#[allow(dead_code)]
+#[path = "../src/data/constants.rs"]
+mod constants;
+
+use constants::{COLLECTIONS, COMMENT_POLICY, TIMEZONE_OFFSET_MINUTES};
+use content_model::comment::{CommentCounts, CommentRecord, NewComment, COMMENTS_FILE};
+use content_model::date::Date;
+use content_render::markdown::render_comment;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Comments by `{collection}/{id}`.
+type Store = HashMap<String, Vec<CommentRecord>>;
+
+fn main() {
+    let port = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "8787".to_string());
+    let listener = TcpListener::bind(format!("127.0.0.1:{}", port)).expect("cannot bind port");
+    let mut store = seed();
+    println!("comments API on http://localhost:{}", port);
+
+    for stream in listener.incoming().flatten() {
+        if let Err(e) = handle(stream, &mut store) {
+            eprintln!("request failed: {}", e);
+        }
+    }
+}
+
+/// Loads the static comments of every entry folder.
+fn seed() -> Store {
+    let mut store = Store::new();
+    for collection in COLLECTIONS {
+        let dir = Path::new("public/content").join(collection.name);
+        for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+            let Ok(content) = fs::read_to_string(entry.path().join(COMMENTS_FILE)) else {
+                continue;
+            };
+            if let Ok(mut comments) = serde_json::from_str::<Vec<CommentRecord>>(&content) {
+                for comment in &mut comments {
+                    comment.html = Some(render_comment(&comment.body, &COMMENT_POLICY));
+                }
+                let id = entry.file_name().to_string_lossy().to_string();
+                store.insert(format!("{}/{}", collection.name, id), comments);
+            }
+        }
+    }
+    store
+}
+
+fn handle(mut stream: TcpStream, store: &mut Store) -> std::io::Result<()> {
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header)?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default().trim_matches('/');
+    println!("{} /{}", method, path);
+    let (status, response) = respond(method, path, &body, store);
+    // Errors are a sentence the comment form shows as is
+    let content_type = if status.starts_with('2') {
+        "application/json"
+    } else {
+        "text/plain; charset=utf-8"
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\n\
+         Access-Control-Allow-Origin: *\r\n\
+         Access-Control-Allow-Methods: GET, POST, OPTIONS\r\n\
+         Access-Control-Allow-Headers: Content-Type\r\n\
+         Content-Type: {}\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        status,
+        content_type,
+        response.len(),
+        response
+    )
+}
+
+fn respond(method: &str, path: &str, body: &[u8], store: &mut Store) -> (&'static str, String) {
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    match (method, segments.as_slice()) {
+        ("OPTIONS", _) => ("204 No Content", String::new()),
+        ("GET", [collection]) => {
+            let prefix = format!("{}/", collection);
+            let counts: CommentCounts = store
+                .iter()
+                .filter_map(|(key, comments)| {
+                    let id = key.strip_prefix(&prefix)?;
+                    (!comments.is_empty()).then(|| (id.to_string(), comments.len()))
+                })
+                .collect();
+            ("200 OK", serde_json::to_string(&counts).unwrap())
+        }
+        ("GET", [collection, id]) => {
+            let comments = store
+                .get(&format!("{}/{}", collection, id))
+                .cloned()
+                .unwrap_or_default();
+            ("200 OK", serde_json::to_string(&comments).unwrap())
+        }
+        ("POST", [collection, id]) => {
+            let Ok(comment) = serde_json::from_slice::<NewComment>(body) else {
+                return ("400 Bad Request", "Malformed comment.".to_string());
+            };
+            if let Some(problem) = comment.problem() {
+                return ("422 Unprocessable Entity", problem.to_string());
+            }
+            let comments = store.entry(format!("{}/{}", collection, id)).or_default();
+            if let Some(parent) = &comment.parent {
+                if !comments.iter().any(|other| &other.id == parent) {
+                    return ("422 Unprocessable Entity", "Unknown parent comment.".into());
+                }
+            }
+            let mut next = comments.len() + 1;
+            while comments.iter().any(|other| other.id == next.to_string()) {
+                next += 1;
+            }
+            let body = comment.body.trim().to_string();
+            let record = CommentRecord {
+                id: next.to_string(),
+                parent: comment.parent,
+                author: comment.author.trim().to_string(),
+                date: today(),
+                avatar_url: None,
+                html: Some(render_comment(&body, &COMMENT_POLICY)),
+                body,
+            };
+            comments.push(record.clone());
+            ("201 Created", serde_json::to_string(&record).unwrap())
+        }
+        _ => ("404 Not Found", "Not found.".to_string()),
+    }
+}
+
+fn today() -> Date {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() as i64)
+        .unwrap_or_default();
+    Date::from_unix(secs, TIMEZONE_OFFSET_MINUTES)
+}
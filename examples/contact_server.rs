@@ -0,0 +1,151 @@
+//! A local stand-in for a form service, for trying a contact form endpoint out.
+//!
+//! ```sh
+//! cargo run --example contact_server            # http://localhost:8788
+//! cargo run --example contact_server -- 9000    # another port
+//! ```
+//!
+//! Takes messages POSTed to any path, JSON or form-encoded, and prints them.
+//! Like Formspree it answers `{"ok":true}`, or a reason the form shows when
+//! a field is missing. It answers every origin, so `dx serve` can reach it
+//! from another port.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+
+fn main() {
+    let port = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "8788".to_string());
+    let listener = TcpListener::bind(format!("127.0.0.1:{}", port)).expect("cannot bind port");
+    println!("contact endpoint on http://localhost:{}", port);
+
+    for stream in listener.incoming().flatten() {
+        if let Err(e) = handle(stream) {
+            eprintln!("request failed: {}", e);
+        }
+    }
+}
+
+fn handle(mut stream: TcpStream) -> std::io::Result<()> {
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut content_length = 0;
+    let mut content_type = String::new();
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header)?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            } else if name.eq_ignore_ascii_case("content-type") {
+                content_type = value.trim().to_string();
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    let method = request_line.split_whitespace().next().unwrap_or_default();
+    println!("{}", request_line.trim_end());
+    let (status, response) = respond(method, &content_type, &body);
+    // Errors are a sentence the contact form shows as is
+    let content_type = if status.starts_with('2') {
+        "application/json"
+    } else {
+        "text/plain; charset=utf-8"
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\n\
+         Access-Control-Allow-Origin: *\r\n\
+         Access-Control-Allow-Methods: POST, OPTIONS\r\n\
+         Access-Control-Allow-Headers: Content-Type, Accept\r\n\
+         Content-Type: {}\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        status,
+        content_type,
+        response.len(),
+        response
+    )
+}
+
+fn respond(method: &str, content_type: &str, body: &[u8]) -> (&'static str, String) {
+    match method {
+        "OPTIONS" => ("204 No Content", String::new()),
+        "POST" => {
+            let body = String::from_utf8_lossy(body);
+            let fields: HashMap<String, String> = if content_type.starts_with("application/json") {
+                serde_json::from_str(&body).unwrap_or_default()
+            } else {
+                body.split('&')
+                    .filter_map(|pair| pair.split_once('='))
+                    .map(|(name, value)| (form_decode(name), form_decode(value)))
+                    .collect()
+            };
+            let field = |name: &str| fields.get(name).map_or("", |value| value.trim());
+            if let Some(missing) = ["name", "email", "message"]
+                .into_iter()
+                .find(|name| field(name).is_empty())
+            {
+                return (
+                    "422 Unprocessable Entity",
+                    format!("The {} is missing.", missing),
+                );
+            }
+            if !field("email").contains('@') {
+                return (
+                    "422 Unprocessable Entity",
+                    "The email address is not valid.".to_string(),
+                );
+            }
+            println!(
+                "--- {}\nFrom: {} <{}>\n\n{}\n---",
+                field("subject"),
+                field("name"),
+                field("email"),
+                field("message")
+            );
+            ("200 OK", r#"{"ok":true}"#.to_string())
+        }
+        _ => (
+            "405 Method Not Allowed",
+            "Send messages with POST.".to_string(),
+        ),
+    }
+}
+
+/// Decodes one `application/x-www-form-urlencoded` name or value.
+fn form_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                match std::str::from_utf8(&bytes[i + 1..i + 3])
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
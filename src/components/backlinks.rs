@@ -0,0 +1,48 @@
+use crate::components::SectionTitle;
+use crate::data::collection::Collection;
+use crate::data::constants::COLLECTIONS;
+use crate::data::rendered::RelatedEntry;
+use crate::Route;
+use dioxus::prelude::*;
+
+/// "Referenced by" list of the entries linking to this one with `[[wiki links]]`.
+#[component]
+pub fn Backlinks(entries: Vec<RelatedEntry>) -> Element {
+    if entries.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        section { class: "flex flex-col gap-6",
+            SectionTitle { title: "Referenced by" }
+            ul { class: "flex flex-col gap-2",
+                for entry in entries {
+                    li { key: "{entry.content_type}/{entry.id}",
+                        Link {
+                            class: "group flex items-start gap-3 rounded-lg px-4 py-3 hover:bg-text-dark/5 dark:hover:bg-white/5 transition-colors",
+                            to: Route::entry(&entry.content_type, &entry.id),
+                            span { class: "material-symbols-outlined text-text-dark/40 dark:text-gray-500 mt-0.5",
+                                "subdirectory_arrow_right"
+                            }
+                            div { class: "flex flex-col gap-1 min-w-0",
+                                p { class: "font-semibold text-text-dark dark:text-white group-hover:text-primary-light transition-colors",
+                                    "{entry.title}"
+                                    if let Some(collection) = Collection::by_name(COLLECTIONS, &entry.content_type) {
+                                        span { class: "ml-2 text-xs font-medium uppercase tracking-wide text-text-dark/50 dark:text-gray-400",
+                                            "{collection.item}"
+                                        }
+                                    }
+                                }
+                                if !entry.description.is_empty() {
+                                    p { class: "text-sm text-text-dark/70 dark:text-[#D4D4D4] line-clamp-2",
+                                        "{entry.description}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,47 +1,175 @@
+use crate::components::Markup;
+use crate::data::collection::Collection;
+use crate::data::comment::{thread, CommentRecord, NewComment, Thread};
+use crate::data::comments::{comment_body, provider, CommentProvider};
+use crate::data::utils::{format_date, relative_date};
+use crate::hooks::{use_eager_resource, use_hydrated};
 use dioxus::prelude::*;
 
-#[derive(Props, Clone, PartialEq)]
-pub struct Comment {
-    pub author: String,
-    pub date: String,
-    pub avatar_url: String,
-    pub text: String,
-}
+/// Replies nest this deep; the deepest comments cannot be answered in place.
+const MAX_DEPTH: usize = 3;
 
+/// The comment section of an entry, loaded from the configured backend.
 #[component]
-pub fn Comments(comments: Vec<Comment>) -> Element {
+pub fn Comments(collection: &'static Collection, id: String) -> Element {
+    let entry_id = id.clone();
+    let mut comments = use_eager_resource(move || {
+        let id = entry_id.clone();
+        async move { provider().load(collection.name, &id).await }
+    });
+    // Posted here since the comments were loaded
+    let mut posted = use_signal(Vec::<CommentRecord>::new);
+    let mut replying_to = use_signal(|| None::<String>);
+    let accepts_comments = provider().accepts_comments();
+    let onposted = move |comment: CommentRecord| {
+        posted.write().push(comment);
+        replying_to.set(None);
+    };
+
+    let (count, list) = match &*comments.read() {
+        None => (
+            None,
+            rsx! {
+                div { class: "flex items-center gap-3 text-sm text-text-dark/60 dark:text-gray-400",
+                    div { class: "animate-spin rounded-full h-5 w-5 border-t-2 border-b-2 border-primary-light" }
+                    "Loading comments..."
+                }
+            },
+        ),
+        Some(Err(error)) => (
+            None,
+            rsx! {
+                div { class: "flex flex-wrap items-center gap-3 text-sm text-text-dark/70 dark:text-gray-300",
+                    span { class: "material-symbols-outlined text-red-500", "error" }
+                    "Comments could not be loaded. {error}"
+                    button {
+                        class: "text-primary-light font-semibold hover:underline",
+                        onclick: move |_| comments.restart(),
+                        "Try again"
+                    }
+                }
+            },
+        ),
+        Some(Ok(loaded)) => {
+            let mut all = loaded.clone();
+            all.extend(
+                posted
+                    .read()
+                    .iter()
+                    .filter(|comment| !loaded.iter().any(|other| other.id == comment.id))
+                    .cloned(),
+            );
+            let count = all.len();
+            let threads = thread(all);
+            (
+                Some(count),
+                rsx! {
+                    if threads.is_empty() {
+                        p { class: "text-sm text-text-dark/60 dark:text-gray-400",
+                            if accepts_comments {
+                                "No comments yet. Be the first to share your thoughts."
+                            } else {
+                                "No comments yet."
+                            }
+                        }
+                    }
+                    div { class: "space-y-6",
+                        for thread in threads {
+                            CommentThread {
+                                key: "{thread.comment.id}",
+                                thread,
+                                depth: 0,
+                                collection,
+                                entry_id: id.clone(),
+                                replying_to,
+                                accepts_comments,
+                                onposted,
+                            }
+                        }
+                    }
+                },
+            )
+        }
+    };
+
     rsx! {
         div { class: "mt-12",
             h2 { class: "text-2xl font-bold text-text-dark dark:text-white mb-8",
-                "Comments ({comments.len()})"
-            }
-            div { class: "space-y-6",
-                for comment in comments {
-                    CommentEntry {
-                        author: comment.author.clone(),
-                        date: comment.date.clone(),
-                        avatar_url: comment.avatar_url.clone(),
-                        text: comment.text.clone(),
-                    }
+                if let Some(count) = count {
+                    "Comments ({count})"
+                } else {
+                    "Comments"
                 }
             }
+            {list}
             div { class: "mt-12",
-                h3 { class: "text-lg font-semibold text-text-dark dark:text-white mb-4",
-                    "Leave a comment"
+                if accepts_comments {
+                    h3 { class: "text-lg font-semibold text-text-dark dark:text-white mb-4",
+                        "Leave a comment"
+                    }
+                    CommentForm {
+                        collection,
+                        entry_id: id.clone(),
+                        onposted,
+                    }
+                } else {
+                    p { class: "text-sm text-text-dark/60 dark:text-gray-400",
+                        "Comments are closed."
+                    }
                 }
-                form { class: "space-y-4",
-                    div {
-                        textarea {
-                            class: "w-full bg-white dark:bg-[#111111] border border-text-dark/10 dark:border-white/10 rounded-xl p-4 text-text-dark dark:text-white placeholder:text-text-dark/40 dark:placeholder:text-gray-600 focus:ring-2 focus:ring-primary focus:border-transparent transition-all outline-none resize-y min-h-[140px]",
-                            placeholder: "Write your comment...",
-                            rows: 4,
-                        }
+            }
+        }
+    }
+}
+
+#[component]
+fn CommentThread(
+    thread: Thread,
+    depth: usize,
+    collection: &'static Collection,
+    entry_id: String,
+    replying_to: Signal<Option<String>>,
+    accepts_comments: bool,
+    onposted: EventHandler<CommentRecord>,
+) -> Element {
+    let comment = thread.comment;
+    let comment_id = comment.id.clone();
+    let is_replying = replying_to.read().as_deref() == Some(comment.id.as_str());
+    let can_reply = accepts_comments && depth < MAX_DEPTH;
+
+    rsx! {
+        div { class: "flex flex-col gap-4",
+            CommentEntry {
+                comment: comment.clone(),
+                onreply: if can_reply && !is_replying {
+                    Some(EventHandler::new(move |_| replying_to.set(Some(comment_id.clone()))))
+                } else {
+                    None
+                },
+            }
+            if is_replying {
+                div { class: "pl-6 sm:pl-14",
+                    CommentForm {
+                        collection,
+                        entry_id: entry_id.clone(),
+                        parent: comment.id.clone(),
+                        onposted,
+                        oncancel: move |_| replying_to.set(None),
                     }
-                    div { class: "flex justify-end",
-                        button {
-                            class: "bg-primary-light text-text-dark font-bold py-2.5 px-6 rounded-lg hover:opacity-90 transition-all shadow-lg active:scale-95",
-                            "type": "submit",
-                            "Post Comment"
+                }
+            }
+            if !thread.replies.is_empty() {
+                div { class: "ml-5 pl-6 sm:pl-9 border-l-2 border-text-dark/10 dark:border-white/10 space-y-4",
+                    for reply in thread.replies {
+                        CommentThread {
+                            key: "{reply.comment.id}",
+                            thread: reply,
+                            depth: depth + 1,
+                            collection,
+                            entry_id: entry_id.clone(),
+                            replying_to,
+                            accepts_comments,
+                            onposted,
                         }
                     }
                 }
@@ -51,23 +179,157 @@ pub fn Comments(comments: Vec<Comment>) -> Element {
 }
 
 #[component]
-fn CommentEntry(author: String, date: String, avatar_url: String, text: String) -> Element {
+fn CommentEntry(comment: CommentRecord, onreply: Option<EventHandler<()>>) -> Element {
+    let initial = comment
+        .author
+        .chars()
+        .next()
+        .map(|c| c.to_uppercase().to_string())
+        .unwrap_or_default();
+    let body = comment_body(&comment);
+    // A prerendered page would otherwise keep the comment's age at build time
+    let hydrated = use_hydrated();
+    let age = if hydrated {
+        relative_date(&comment.date)
+    } else {
+        format_date(&comment.date)
+    };
+
     rsx! {
         div { class: "flex gap-4",
-            img {
-                alt: "{author}",
-                class: "size-10 rounded-full object-cover border border-text-dark/10 dark:border-white/10",
-                src: "{avatar_url}",
+            if let Some(avatar_url) = &comment.avatar_url {
+                img {
+                    alt: "{comment.author}",
+                    class: "size-10 shrink-0 rounded-full object-cover border border-text-dark/10 dark:border-white/10",
+                    src: "{avatar_url}",
+                }
+            } else {
+                div {
+                    class: "size-10 shrink-0 rounded-full flex items-center justify-center bg-primary-light/20 text-primary-light font-bold",
+                    "aria-hidden": "true",
+                    "{initial}"
+                }
             }
-            div { class: "flex-1 bg-text-dark/5 dark:bg-[#252525] rounded-xl p-5 border border-text-dark/5 dark:border-white/5",
+            div { class: "flex-1 min-w-0 bg-text-dark/5 dark:bg-[#252525] rounded-xl p-5 border border-text-dark/5 dark:border-white/5",
                 div { class: "flex justify-between items-start mb-2",
                     p { class: "font-semibold text-text-dark dark:text-white text-sm",
-                        "{author}"
+                        "{comment.author}"
+                    }
+                    time {
+                        class: "text-xs text-text-dark/60 dark:text-gray-500",
+                        datetime: "{comment.date}",
+                        title: format_date(&comment.date),
+                        "{age}"
+                    }
+                }
+                div { class: "prose prose-sm max-w-none dark:prose-invert text-text-dark/80 dark:text-gray-300 break-words",
+                    Markup { nodes: body }
+                }
+                if let Some(onreply) = onreply {
+                    button {
+                        class: "mt-3 flex items-center gap-1 text-xs font-semibold text-text-dark/60 dark:text-gray-400 hover:text-primary-light transition-colors",
+                        onclick: move |_| onreply.call(()),
+                        span { class: "material-symbols-outlined text-base", "reply" }
+                        "Reply"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+enum FormStatus {
+    Idle,
+    Submitting,
+    Failed(String),
+}
+
+/// A form posting a new comment, or a reply to `parent`.
+#[component]
+fn CommentForm(
+    collection: &'static Collection,
+    entry_id: String,
+    parent: Option<String>,
+    onposted: EventHandler<CommentRecord>,
+    oncancel: Option<EventHandler<()>>,
+) -> Element {
+    let mut author = use_signal(String::new);
+    let mut body = use_signal(String::new);
+    let mut status = use_signal(|| FormStatus::Idle);
+    let submitting = status() == FormStatus::Submitting;
+    let is_reply = parent.is_some();
+    let field_class = "w-full bg-white dark:bg-[#111111] border border-text-dark/10 dark:border-white/10 rounded-xl text-text-dark dark:text-white placeholder:text-text-dark/40 dark:placeholder:text-gray-600 focus:ring-2 focus:ring-primary focus:border-transparent transition-all outline-none";
+
+    let onsubmit = move |e: FormEvent| {
+        e.prevent_default();
+        if submitting {
+            return;
+        }
+        let comment = NewComment {
+            parent: parent.clone(),
+            author: author().trim().to_string(),
+            body: body().trim().to_string(),
+        };
+        if let Some(problem) = comment.problem() {
+            status.set(FormStatus::Failed(problem.to_string()));
+            return;
+        }
+        let id = entry_id.clone();
+        status.set(FormStatus::Submitting);
+        spawn(async move {
+            match provider().post(collection.name, &id, &comment).await {
+                Ok(posted) => {
+                    body.set(String::new());
+                    status.set(FormStatus::Idle);
+                    onposted.call(posted);
+                }
+                Err(error) => status.set(FormStatus::Failed(error.to_string())),
+            }
+        });
+    };
+
+    rsx! {
+        form { class: "space-y-4", onsubmit,
+            input {
+                class: "{field_class} h-11 px-4",
+                r#type: "text",
+                placeholder: "Your name",
+                "aria-label": "Your name",
+                value: "{author}",
+                oninput: move |e| author.set(e.value()),
+            }
+            textarea {
+                class: "{field_class} p-4 resize-y min-h-[140px]",
+                placeholder: "Write your comment... Markdown is supported.",
+                "aria-label": "Comment",
+                rows: 4,
+                value: "{body}",
+                oninput: move |e| body.set(e.value()),
+            }
+            if let FormStatus::Failed(message) = status() {
+                p { class: "text-sm text-red-600 dark:text-red-400", role: "alert", "{message}" }
+            }
+            div { class: "flex justify-end gap-3",
+                if let Some(oncancel) = oncancel {
+                    button {
+                        class: "py-2.5 px-6 rounded-lg font-semibold text-text-dark/70 dark:text-gray-300 hover:bg-text-dark/5 dark:hover:bg-white/5 transition-all",
+                        "type": "button",
+                        onclick: move |_| oncancel.call(()),
+                        "Cancel"
                     }
-                    p { class: "text-xs text-text-dark/60 dark:text-gray-500", "{date}" }
                 }
-                p { class: "text-text-dark/80 dark:text-gray-300 text-sm leading-relaxed",
-                    "{text}"
+                button {
+                    class: "bg-primary-light text-text-dark font-bold py-2.5 px-6 rounded-lg hover:opacity-90 transition-all shadow-lg active:scale-95 disabled:opacity-60 disabled:cursor-wait",
+                    "type": "submit",
+                    disabled: submitting,
+                    if submitting {
+                        "Posting..."
+                    } else if is_reply {
+                        "Post Reply"
+                    } else {
+                        "Post Comment"
+                    }
                 }
             }
         }
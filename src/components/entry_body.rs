@@ -0,0 +1,320 @@
+use crate::components::shortcodes::ShortcodeView;
+use crate::data::html::{to_html, Node};
+use crate::data::path::is_external;
+use crate::data::utils::get_base_path;
+use crate::Route;
+use dioxus::dioxus_core::{Attribute, AttributeValue, HasAttributes};
+use dioxus::prelude::*;
+use dioxus::router::LinkProps;
+
+/// An entry's rendered body. Runs of markdown share a `.prose` block and
+/// shortcodes are mounted between them, so the article typography does not
+/// leak into their components.
+#[component]
+pub fn EntryBody(body: Vec<Node>) -> Element {
+    let mut blocks = Vec::new();
+    let mut run: Vec<&Node> = Vec::new();
+    for node in &body {
+        if let Node::Shortcode(source) = node {
+            if !run.is_empty() {
+                blocks.push(prose(std::mem::take(&mut run)));
+            }
+            blocks.push(rsx! {
+                div { class: "my-8",
+                    ShortcodeView { source: source.clone() }
+                }
+            });
+        } else {
+            run.push(node);
+        }
+    }
+    if !run.is_empty() {
+        blocks.push(prose(run));
+    }
+
+    rsx! {
+        div { class: "flex flex-col",
+            for block in blocks {
+                {block}
+            }
+        }
+    }
+}
+
+/// Sanitized markup shown outside an entry body, such as a comment.
+#[component]
+pub fn Markup(nodes: Vec<Node>) -> Element {
+    rsx! {
+        for node in &nodes {
+            {render_node(node)}
+        }
+    }
+}
+
+fn prose(nodes: Vec<&Node>) -> Element {
+    rsx! {
+        div { class: "prose max-w-none dark:prose-invert",
+            for node in nodes {
+                {render_node(node)}
+            }
+        }
+    }
+}
+
+/// Displays a document node as the element it describes.
+fn render_node(node: &Node) -> Element {
+    let (tag, attrs, children) = match node {
+        Node::Text(text) => return rsx! { "{text}" },
+        Node::Shortcode(source) => return rsx! { ShortcodeView { source: source.clone() } },
+        Node::Element {
+            tag,
+            attrs,
+            children,
+        } => (tag, attrs, children),
+    };
+    let Some(attributes) = attributes(attrs) else {
+        return raw(node);
+    };
+    let children = rsx! {
+        for child in children {
+            {render_node(child)}
+        }
+    };
+
+    match tag.as_str() {
+        "a" => link(node, attributes, children),
+        "abbr" => rsx! { abbr { ..attributes, {children} } },
+        "aside" => rsx! { aside { ..attributes, {children} } },
+        "b" => rsx! { b { ..attributes, {children} } },
+        "blockquote" => rsx! { blockquote { ..attributes, {children} } },
+        "br" => rsx! { br { ..attributes } },
+        "code" => rsx! { code { ..attributes, {children} } },
+        "dd" => rsx! { dd { ..attributes, {children} } },
+        "del" => rsx! { del { ..attributes, {children} } },
+        "details" => rsx! { details { ..attributes, {children} } },
+        "div" => rsx! { div { ..attributes, {children} } },
+        "dl" => rsx! { dl { ..attributes, {children} } },
+        "dt" => rsx! { dt { ..attributes, {children} } },
+        "em" => rsx! { em { ..attributes, {children} } },
+        "figcaption" => rsx! { figcaption { ..attributes, {children} } },
+        "figure" => rsx! { figure { ..attributes, {children} } },
+        "h1" => rsx! { h1 { ..attributes, {children} } },
+        "h2" => rsx! { h2 { ..attributes, {children} } },
+        "h3" => rsx! { h3 { ..attributes, {children} } },
+        "h4" => rsx! { h4 { ..attributes, {children} } },
+        "h5" => rsx! { h5 { ..attributes, {children} } },
+        "h6" => rsx! { h6 { ..attributes, {children} } },
+        "hr" => rsx! { hr { ..attributes } },
+        "i" => rsx! { i { ..attributes, {children} } },
+        "img" => rsx! { img { ..attributes } },
+        "input" => rsx! { input { ..attributes } },
+        "kbd" => rsx! { kbd { ..attributes, {children} } },
+        "li" => rsx! { li { ..attributes, {children} } },
+        "mark" => rsx! { mark { ..attributes, {children} } },
+        "ol" => rsx! { ol { ..attributes, {children} } },
+        "p" => rsx! { p { ..attributes, {children} } },
+        "picture" => rsx! { picture { ..attributes, {children} } },
+        "pre" => rsx! { pre { ..attributes, {children} } },
+        "s" => rsx! { s { ..attributes, {children} } },
+        "small" => rsx! { small { ..attributes, {children} } },
+        "source" => rsx! { source { ..attributes } },
+        "span" => rsx! { span { ..attributes, {children} } },
+        "strong" => rsx! { strong { ..attributes, {children} } },
+        "sub" => rsx! { sub { ..attributes, {children} } },
+        "summary" => rsx! { summary { ..attributes, {children} } },
+        "sup" => rsx! { sup { ..attributes, {children} } },
+        "table" => rsx! { table { ..attributes, {children} } },
+        "tbody" => rsx! { tbody { ..attributes, {children} } },
+        "td" => rsx! { td { ..attributes, {children} } },
+        "th" => rsx! { th { ..attributes, {children} } },
+        "thead" => rsx! { thead { ..attributes, {children} } },
+        "tr" => rsx! { tr { ..attributes, {children} } },
+        "u" => rsx! { u { ..attributes, {children} } },
+        "ul" => rsx! { ul { ..attributes, {children} } },
+        // MathML, embeds and other markup without a case above
+        _ => raw(node),
+    }
+}
+
+/// Site links navigate with the router; links to other sites open with
+/// `rel="noopener"` and an external-link icon.
+fn link(node: &Node, attributes: Vec<Attribute>, children: Element) -> Element {
+    let href = node.attr("href").unwrap_or_default();
+    if let Some(route) = internal_route(href) {
+        // `Link` writes these from its own props; `title`, `id`, `aria-*` and the rest pass through
+        let mut props = LinkProps::builder()
+            .to(route)
+            .class(node.attr("class").map(str::to_string))
+            .rel(node.attr("rel").map(str::to_string))
+            .new_tab(node.attr("target") == Some("_blank"))
+            .children(children);
+        for attribute in attributes {
+            if !matches!(attribute.name, "href" | "class" | "rel" | "target") {
+                props = props.push_attribute(
+                    attribute.name,
+                    attribute.namespace,
+                    attribute.value,
+                    attribute.volatile,
+                );
+            }
+        }
+        let props = props.build();
+        return rsx! {
+            Link { ..props }
+        };
+    }
+    if is_external(href) {
+        let has_image = matches!(node, Node::Element { children, .. } if children.iter().any(|child| {
+            matches!(child, Node::Element { tag, .. } if tag == "img" || tag == "picture")
+        }));
+        let attributes: Vec<Attribute> = attributes
+            .into_iter()
+            .filter(|attribute| attribute.name != "rel")
+            .collect();
+        return rsx! {
+            a { rel: "noopener", ..attributes,
+                {children}
+                if !has_image {
+                    span {
+                        class: "material-symbols-outlined external-link-icon",
+                        "aria-hidden": "true",
+                        "open_in_new"
+                    }
+                }
+            }
+        };
+    }
+    rsx! {
+        a { ..attributes, {children} }
+    }
+}
+
+/// The page a rendered `href` leads to, when the router can show it without a
+/// reload. Section links stay plain anchors so the scroll handling sees them.
+fn internal_route(href: &str) -> Option<Route> {
+    let path = href.strip_prefix(&get_base_path())?;
+    if !path.starts_with('/') || path.contains('#') {
+        return None;
+    }
+    match path.parse::<Route>() {
+        Ok(Route::NotFound { .. }) | Err(_) => None,
+        Ok(route) => Some(route),
+    }
+}
+
+/// Markup without a case in [`render_node`], inserted as HTML. Bodies are
+/// sanitized when they are rendered, so this only ever sees allowed markup.
+fn raw(node: &Node) -> Element {
+    let html = to_html(std::slice::from_ref(node));
+    rsx! {
+        span { dangerous_inner_html: html }
+    }
+}
+
+/// The node's attributes for Dioxus, or `None` if one of them has a name
+/// Dioxus needs to know statically and it is not in this list.
+fn attributes(attrs: &[(String, String)]) -> Option<Vec<Attribute>> {
+    attrs
+        .iter()
+        .map(|(name, value)| {
+            let name = attribute_name(name)?;
+            let value = match name {
+                "checked" | "disabled" | "open" | "hidden" | "reversed" => {
+                    AttributeValue::Bool(true)
+                }
+                _ => AttributeValue::Text(value.clone()),
+            };
+            Some(Attribute::new(name, value, None, false))
+        })
+        .collect()
+}
+
+fn attribute_name(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "align" => "align",
+        "alt" => "alt",
+        "checked" => "checked",
+        "cite" => "cite",
+        "class" => "class",
+        "colspan" => "colspan",
+        "datetime" => "datetime",
+        "decoding" => "decoding",
+        "dir" => "dir",
+        "disabled" => "disabled",
+        "height" => "height",
+        "hidden" => "hidden",
+        "href" => "href",
+        "id" => "id",
+        "lang" => "lang",
+        "loading" => "loading",
+        "media" => "media",
+        "open" => "open",
+        "rel" => "rel",
+        "reversed" => "reversed",
+        "role" => "role",
+        "rowspan" => "rowspan",
+        "scope" => "scope",
+        "sizes" => "sizes",
+        "src" => "src",
+        "srcset" => "srcset",
+        "start" => "start",
+        "style" => "style",
+        "target" => "target",
+        "title" => "title",
+        "type" => "type",
+        "width" => "width",
+        // Content policies allow every `aria-*` attribute
+        _ => return ARIA_ATTRIBUTES.iter().find(|aria| **aria == name).copied(),
+    })
+}
+
+/// The states and properties of WAI-ARIA 1.2.
+const ARIA_ATTRIBUTES: &[&str] = &[
+    "aria-activedescendant",
+    "aria-atomic",
+    "aria-autocomplete",
+    "aria-busy",
+    "aria-checked",
+    "aria-colcount",
+    "aria-colindex",
+    "aria-colspan",
+    "aria-controls",
+    "aria-current",
+    "aria-describedby",
+    "aria-description",
+    "aria-details",
+    "aria-disabled",
+    "aria-errormessage",
+    "aria-expanded",
+    "aria-flowto",
+    "aria-haspopup",
+    "aria-hidden",
+    "aria-invalid",
+    "aria-keyshortcuts",
+    "aria-label",
+    "aria-labelledby",
+    "aria-level",
+    "aria-live",
+    "aria-modal",
+    "aria-multiline",
+    "aria-multiselectable",
+    "aria-orientation",
+    "aria-owns",
+    "aria-placeholder",
+    "aria-posinset",
+    "aria-pressed",
+    "aria-readonly",
+    "aria-relevant",
+    "aria-required",
+    "aria-roledescription",
+    "aria-rowcount",
+    "aria-rowindex",
+    "aria-rowspan",
+    "aria-selected",
+    "aria-setsize",
+    "aria-sort",
+    "aria-valuemax",
+    "aria-valuemin",
+    "aria-valuenow",
+    "aria-valuetext",
+];
@@ -1,5 +1,10 @@
 use crate::components::{Card, CategoryFilter, Container, Hero, SearchBar, Section};
+use crate::data::collection::{Collection, PublishStatus};
+use crate::data::comments::{provider, CommentProvider};
+use crate::data::responsive::ResponsiveImage;
+use crate::data::search::load_search_index;
 use crate::data::utils::get_base_path;
+use crate::hooks::use_eager_resource;
 use crate::Route;
 use dioxus::prelude::*;
 
@@ -9,48 +14,75 @@ pub struct GalleryItem {
     pub title: String,
     pub description: String,
     pub image_url: String,
+    pub image: Option<ResponsiveImage>,
     pub tags: Vec<String>,
+    pub status: PublishStatus,
 }
 
 #[component]
 pub fn ContentGallery(
-    title: String,
-    subtitle: String,
-    search_placeholder: String,
+    /// Collection the items belong to, for the heading, search and links.
+    collection: &'static Collection,
     items: Vec<GalleryItem>,
     categories: Vec<String>,
-    route_factory: fn(String) -> Route,
     centered_hero: Option<bool>,
 ) -> Element {
     let mut search_query = use_signal(|| "".to_string());
     let mut selected_category = use_signal(|| "All".to_string());
 
-    let filtered_items = items.into_iter().filter(|item| {
-        let matches_search = item
-            .title
-            .to_lowercase()
-            .contains(&search_query().to_lowercase())
-            || item
-                .description
-                .to_lowercase()
-                .contains(&search_query().to_lowercase());
-        let matches_category =
-            selected_category() == "All" || item.tags.contains(&selected_category());
-        matches_search && matches_category
+    // The full-text index is only fetched once the reader starts typing
+    let search_hits = use_resource(move || {
+        let query = search_query();
+        async move {
+            if query.trim().is_empty() {
+                return Vec::new();
+            }
+            match load_search_index().await {
+                Some(index) => index.search(&query, collection.name),
+                None => Vec::new(),
+            }
+        }
     });
+    let hits = search_hits.read().clone().unwrap_or_default();
+    // Cards go without counts when the backend cannot provide them
+    let comment_counts = use_eager_resource(move || async move {
+        provider().counts(collection.name).await.unwrap_or_default()
+    });
+    let comment_counts = comment_counts.read().clone().unwrap_or_default();
+
+    let mut filtered_items: Vec<(Option<usize>, GalleryItem)> = items
+        .into_iter()
+        .filter_map(|item| {
+            let rank = hits.iter().position(|hit| hit.id == item.id);
+            let matches_search = rank.is_some()
+                || item
+                    .title
+                    .to_lowercase()
+                    .contains(&search_query().to_lowercase())
+                || item
+                    .description
+                    .to_lowercase()
+                    .contains(&search_query().to_lowercase());
+            let matches_category =
+                selected_category() == "All" || item.tags.contains(&selected_category());
+            (item.status.is_listed() && matches_search && matches_category).then_some((rank, item))
+        })
+        .collect();
+    // Ranked full-text hits first, then plain title/description matches in their original order
+    filtered_items.sort_by_key(|(rank, _)| rank.unwrap_or(usize::MAX));
 
     rsx! {
         Container {
             main { class: "flex flex-col gap-12 mt-8 md:mt-16",
                 Hero {
-                    title: "{title}",
-                    subtitle: "{subtitle}",
+                    title: collection.title,
+                    subtitle: collection.subtitle,
                     centered: centered_hero.unwrap_or(true),
                     children: rsx! {
                         div { class: "flex flex-col gap-6 w-full mt-4",
                             div { class: "w-full mx-auto max-w-2xl",
                                 SearchBar {
-                                    placeholder: "{search_placeholder}",
+                                    placeholder: collection.search_placeholder,
                                     value: search_query(),
                                     oninput: move |e: FormEvent| search_query.set(e.value()),
                                 }
@@ -65,13 +97,18 @@ pub fn ContentGallery(
                 }
 
                 Section { class: "grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 gap-8 mb-20 px-4",
-                    for item in filtered_items {
+                    for (rank, item) in filtered_items {
                         Card {
                             title: item.title.clone(),
-                            description: item.description.clone(),
+                            description: rank
+                                .and_then(|rank| hits[rank].snippet.clone())
+                                .unwrap_or_else(|| item.description.clone()),
                             image_url: format!("{}/{}", get_base_path(), item.image_url),
+                            image: item.image.clone(),
                             tags: item.tags.clone(),
-                            link_to: route_factory(item.id.clone()),
+                            link_to: Route::entry(collection.name, &item.id),
+                            draft: item.status.is_draft(),
+                            comments: comment_counts.get(&item.id).copied(),
                         }
                     }
                 }
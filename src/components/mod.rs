@@ -1,14 +1,25 @@
+pub mod backlinks;
 pub mod call_to_action;
 pub mod comments;
+pub mod entry_body;
 pub mod gallery;
+pub mod related;
+pub mod series_nav;
 pub mod share_buttons;
+pub mod shortcodes;
+pub mod toc;
 pub mod ui;
 
+pub use backlinks::Backlinks;
 pub use call_to_action::CallToAction;
-pub use comments::{Comment, Comments};
+pub use comments::Comments;
+pub use entry_body::{EntryBody, Markup};
 pub use gallery::{ContentGallery, GalleryItem};
+pub use related::RelatedContent;
+pub use series_nav::SeriesNav;
 pub use share_buttons::ShareButtons;
+pub use toc::{ReadingProgress, TableOfContents, TOC_MIN_ENTRIES};
 pub use ui::{
-    Badge, Card, CategoryFilter, Container, DetailHero, Hero, Input, PrimaryButton, SearchBar,
-    Section, SectionTitle, TextArea, TimelineItem,
+    Badge, Card, CategoryFilter, Container, DetailHero, DraftBadge, Hero, Input, Picture,
+    PrimaryButton, SearchBar, Section, SectionTitle, TextArea, TimelineItem, CARD_IMAGE_SIZES,
 };
@@ -0,0 +1,32 @@
+use crate::components::{Card, SectionTitle};
+use crate::data::rendered::RelatedEntry;
+use crate::data::utils::get_base_path;
+use crate::Route;
+use dioxus::prelude::*;
+
+/// "Related" cards for the entries recommended at build time.
+#[component]
+pub fn RelatedContent(entries: Vec<RelatedEntry>) -> Element {
+    if entries.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        section { class: "flex flex-col gap-6",
+            SectionTitle { title: "Related" }
+            div { class: "grid grid-cols-1 md:grid-cols-2 gap-6",
+                for entry in entries {
+                    Card {
+                        key: "{entry.content_type}/{entry.id}",
+                        title: entry.title.clone(),
+                        description: entry.description.clone(),
+                        image_url: format!("{}/{}", get_base_path(), entry.image_url),
+                        image: entry.cover.clone(),
+                        tags: entry.tags.clone(),
+                        link_to: Route::entry(&entry.content_type, &entry.id),
+                    }
+                }
+            }
+        }
+    }
+}
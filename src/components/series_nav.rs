@@ -0,0 +1,71 @@
+use crate::data::series::Series;
+use crate::Route;
+use dioxus::prelude::*;
+
+/// Box listing every part of a series, with the current entry highlighted and
+/// links to the previous and next parts.
+#[component]
+pub fn SeriesNav(series: Series, collection: &'static str, current: String) -> Element {
+    let Some(position) = series.position(collection, &current) else {
+        return rsx! {};
+    };
+    let (prev, next) = series.neighbours(collection, &current);
+    let total = series.parts.len();
+
+    rsx! {
+        nav {
+            "aria-label": "Series navigation",
+            class: "flex flex-col gap-4 bg-text-dark/5 dark:bg-white/5 p-6 rounded-xl",
+            div { class: "flex flex-wrap items-baseline justify-between gap-2",
+                Link {
+                    class: "text-lg font-bold text-text-dark dark:text-white hover:text-primary-light transition-colors",
+                    to: Route::SeriesPage {
+                        id: series.id.clone(),
+                    },
+                    span { class: "material-symbols-outlined text-base align-middle mr-2", "collections_bookmark" }
+                    "{series.title}"
+                }
+                span { class: "text-sm text-text-dark/60 dark:text-gray-400",
+                    "Part {position + 1} of {total}"
+                }
+            }
+            ol { class: "flex flex-col gap-1 text-sm",
+                for (idx , part) in series.parts.iter().enumerate() {
+                    li { key: "{part.id}",
+                        if idx == position {
+                            span {
+                                "aria-current": "page",
+                                class: "block px-3 py-1.5 rounded-lg bg-primary-light/10 font-semibold text-primary-light",
+                                "{idx + 1}. {part.title}"
+                            }
+                        } else {
+                            Link {
+                                class: "block px-3 py-1.5 rounded-lg text-text-dark/70 dark:text-gray-300 hover:bg-text-dark/5 dark:hover:bg-white/5 transition-colors",
+                                to: Route::entry(&part.collection, &part.id),
+                                "{idx + 1}. {part.title}"
+                            }
+                        }
+                    }
+                }
+            }
+            div { class: "flex justify-between gap-4 text-sm font-medium",
+                if let Some(prev) = prev {
+                    Link {
+                        class: "text-primary-light hover:underline",
+                        to: Route::entry(&prev.collection, &prev.id),
+                        "← {prev.title}"
+                    }
+                } else {
+                    span {}
+                }
+                if let Some(next) = next {
+                    Link {
+                        class: "text-primary-light hover:underline text-right",
+                        to: Route::entry(&next.collection, &next.id),
+                        "{next.title} →"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use crate::components::{Card, TimelineItem};
+use crate::data::collection::Collection;
+use crate::data::constants::COLLECTIONS;
+use crate::data::content::fetch_entries;
+use crate::data::shortcode::Shortcode;
+use crate::data::utils::get_base_path;
+use crate::hooks::use_eager_resource;
+use crate::Route;
+use dioxus::prelude::*;
+
+/// The component registered for a shortcode, or an error box explaining why there is none.
+#[component]
+pub fn ShortcodeView(source: String) -> Element {
+    let shortcode = match Shortcode::parse(&source) {
+        Ok(shortcode) => shortcode,
+        Err(message) => return rsx! { ShortcodeError { source, message } },
+    };
+    if let Err(message) = shortcode.validate(COLLECTIONS) {
+        return rsx! { ShortcodeError { source, message } };
+    }
+    let arg = |key: &str| shortcode.arg(key).map(str::to_string);
+
+    if let Some(collection) = shortcode.entry_collection(COLLECTIONS) {
+        return rsx! {
+            EntryCard {
+                collection: collection.name,
+                id: arg("id").unwrap_or_default(),
+                source,
+            }
+        };
+    }
+    match shortcode.name.as_str() {
+        "youtube" => rsx! {
+            YouTubeEmbed {
+                id: arg("id").unwrap_or_default(),
+                title: arg("title"),
+                start: arg("start"),
+            }
+        },
+        "github" => rsx! {
+            GitHubRepo {
+                repo: arg("repo").unwrap_or_default(),
+                description: arg("description"),
+            }
+        },
+        "timeline" => rsx! {
+            div { class: "relative pl-6 border-l-2 border-primary-light/30",
+                TimelineItem {
+                    date: arg("date").unwrap_or_default(),
+                    title: arg("title").unwrap_or_default(),
+                    description: arg("description").unwrap_or_default(),
+                    is_last: true,
+                }
+            }
+        },
+        // Registered in `SHORTCODES` but not matched above
+        name => rsx! {
+            ShortcodeError { source, message: format!("`{}` has no component", name) }
+        },
+    }
+}
+
+/// Shown in place of a shortcode that cannot be displayed.
+#[component]
+fn ShortcodeError(source: String, message: String) -> Element {
+    rsx! {
+        div {
+            role: "alert",
+            class: "flex items-start gap-3 rounded-xl border border-red-500/60 bg-red-500/10 px-5 py-4 text-red-700 dark:text-red-300",
+            span { class: "material-symbols-outlined", "error" }
+            div { class: "flex flex-col gap-1 min-w-0",
+                p { class: "font-bold", "Shortcode error: {message}" }
+                code { class: "text-sm break-all opacity-80", "{source}" }
+            }
+        }
+    }
+}
+
+/// Card linking to another entry of the site.
+#[component]
+fn EntryCard(collection: &'static str, id: String, source: String) -> Element {
+    let entry_id = id.clone();
+    let entry = use_eager_resource(move || {
+        let id = entry_id.clone();
+        async move {
+            let collection = Collection::by_name(COLLECTIONS, collection)?;
+            fetch_entries(collection)
+                .await
+                .into_iter()
+                .find(|entry| entry.id == id)
+        }
+    });
+
+    let entry = entry.read().clone();
+    match entry {
+        Some(Some(entry)) => rsx! {
+            div { class: "max-w-md",
+                Card {
+                    title: entry.title,
+                    description: entry.description,
+                    image_url: format!("{}/{}", get_base_path(), entry.image_url),
+                    image: entry.cover,
+                    tags: entry.tags,
+                    link_to: Route::entry(collection, &entry.id),
+                    draft: entry.status.is_draft(),
+                }
+            }
+        },
+        Some(None) => rsx! {
+            ShortcodeError { source, message: format!("no entry `{}` in {}", id, collection) }
+        },
+        None => rsx! {
+            div { class: "max-w-md h-80 rounded-lg bg-text-dark/5 dark:bg-white/5 animate-pulse" }
+        },
+    }
+}
+
+/// YouTube video that loads the player only when clicked, showing the
+/// thumbnail until then.
+#[component]
+fn YouTubeEmbed(id: String, title: Option<String>, start: Option<String>) -> Element {
+    let mut playing = use_signal(|| false);
+    let title = title.unwrap_or_else(|| "YouTube video".to_string());
+    let start = start
+        .and_then(|start| start.parse::<u32>().ok())
+        .map(|start| format!("&start={}", start))
+        .unwrap_or_default();
+
+    rsx! {
+        div { class: "relative w-full aspect-video overflow-hidden rounded-xl bg-black shadow-lg",
+            if playing() {
+                iframe {
+                    class: "absolute inset-0 w-full h-full",
+                    src: "https://www.youtube-nocookie.com/embed/{id}?autoplay=1{start}",
+                    title: "{title}",
+                    allow: "accelerometer; autoplay; clipboard-write; encrypted-media; gyroscope; picture-in-picture",
+                    allowfullscreen: true,
+                    border: "0",
+                }
+            } else {
+                button {
+                    r#type: "button",
+                    class: "group absolute inset-0 w-full h-full cursor-pointer",
+                    "aria-label": "Play {title}",
+                    onclick: move |_| playing.set(true),
+                    img {
+                        class: "absolute inset-0 w-full h-full object-cover opacity-90 group-hover:opacity-100 transition-opacity",
+                        src: "https://i.ytimg.com/vi/{id}/hqdefault.jpg",
+                        alt: "",
+                        loading: "lazy",
+                    }
+                    span { class: "absolute left-4 right-4 top-4 text-left text-white font-semibold drop-shadow truncate",
+                        "{title}"
+                    }
+                    span { class: "absolute top-1/2 left-1/2 -translate-x-1/2 -translate-y-1/2 flex items-center justify-center w-16 h-16 rounded-full bg-red-600 text-white shadow-xl group-hover:scale-110 transition-transform",
+                        span { class: "material-symbols-outlined text-4xl", "play_arrow" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Card linking to a GitHub repository.
+#[component]
+fn GitHubRepo(repo: String, description: Option<String>) -> Element {
+    let (owner, name) = repo.split_once('/').unwrap_or(("", repo.as_str()));
+    rsx! {
+        a {
+            href: "https://github.com/{repo}",
+            target: "_blank",
+            rel: "noopener noreferrer",
+            class: "flex items-center gap-4 max-w-md rounded-lg border border-text-dark/10 dark:border-white/10 bg-white dark:bg-[#2a2a2a] px-5 py-4 hover:shadow-lg dark:hover:bg-white/5 transition-all group",
+            span { class: "material-symbols-outlined text-3xl text-text-dark/60 dark:text-gray-400",
+                "book_2"
+            }
+            div { class: "flex flex-col min-w-0",
+                p { class: "font-bold text-text-dark dark:text-white group-hover:text-primary-light transition-colors truncate",
+                    if !owner.is_empty() {
+                        span { class: "font-normal text-text-dark/60 dark:text-gray-400", "{owner}/" }
+                    }
+                    "{name}"
+                }
+                if let Some(description) = description {
+                    p { class: "text-sm text-text-dark/70 dark:text-[#D4D4D4]", "{description}" }
+                }
+            }
+            span { class: "material-symbols-outlined ml-auto text-text-dark/40 dark:text-gray-500",
+                "open_in_new"
+            }
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use crate::data::rendered::TocEntry;
+use dioxus::prelude::*;
+
+/// Fewest headings worth a table of contents.
+pub const TOC_MIN_ENTRIES: usize = 2;
+
+/// Outline of an entry's top two heading levels, linking to `page#id`. The
+/// `active` heading (or the section it belongs to) is highlighted.
+/// `collapsible` renders it as a closed `<details>` block for narrow screens.
+#[component]
+pub fn TableOfContents(
+    entries: Vec<TocEntry>,
+    page: String,
+    active: Option<String>,
+    #[props(default)] collapsible: bool,
+) -> Element {
+    let Some(top) = entries.iter().map(|entry| entry.level).min() else {
+        return rsx! {};
+    };
+    // Nested headings highlight the entry they belong to
+    let mut highlighted = None;
+    for entry in &entries {
+        if entry.level <= top + 1 {
+            highlighted = Some(entry.id.as_str());
+        }
+        if active.as_deref() == Some(entry.id.as_str()) {
+            break;
+        }
+    }
+    let highlighted = active.as_ref().and(highlighted);
+
+    let list = rsx! {
+        ol { class: "flex flex-col gap-1 text-sm border-l border-text-dark/10 dark:border-white/10",
+            for entry in entries.iter().filter(|entry| entry.level <= top + 1) {
+                li { key: "{entry.id}",
+                    a {
+                        href: "{page}#{entry.id}",
+                        "aria-current": if highlighted == Some(entry.id.as_str()) { "location" },
+                        class: if highlighted == Some(entry.id.as_str()) { "block -ml-px border-l-2 border-primary-light py-1 font-semibold text-primary-light" } else { "block -ml-px border-l-2 border-transparent py-1 text-text-dark/60 dark:text-gray-400 hover:text-text-dark dark:hover:text-white transition-colors" },
+                        padding_left: if entry.level > top { "1.75rem" } else { "1rem" },
+                        "{entry.text}"
+                    }
+                }
+            }
+        }
+    };
+
+    if collapsible {
+        rsx! {
+            details { class: "group bg-text-dark/5 dark:bg-white/5 rounded-xl p-4",
+                summary { class: "flex items-center justify-between cursor-pointer list-none font-bold text-text-dark dark:text-white",
+                    "On this page"
+                    span { class: "material-symbols-outlined transition-transform group-open:rotate-180",
+                        "expand_more"
+                    }
+                }
+                div { class: "mt-4", {list} }
+            }
+        }
+    } else {
+        rsx! {
+            nav { "aria-label": "Table of contents", class: "flex flex-col gap-4",
+                p { class: "text-xs font-bold uppercase tracking-wider text-text-dark/50 dark:text-gray-500",
+                    "On this page"
+                }
+                {list}
+            }
+        }
+    }
+}
+
+/// Thin bar along the top of the viewport showing how much of the page has been read.
+#[component]
+pub fn ReadingProgress(progress: f64) -> Element {
+    let percent = (progress.clamp(0.0, 1.0) * 100.0).round();
+    rsx! {
+        div {
+            role: "progressbar",
+            "aria-label": "Reading progress",
+            "aria-valuemin": "0",
+            "aria-valuemax": "100",
+            "aria-valuenow": "{percent}",
+            class: "fixed top-0 left-0 right-0 h-1 z-[60] pointer-events-none",
+            div {
+                class: "h-full bg-primary-light transition-[width] duration-150 ease-out",
+                width: "{percent}%",
+            }
+        }
+    }
+}
@@ -1,3 +1,6 @@
+use crate::components::{Picture, CARD_IMAGE_SIZES};
+use crate::data::responsive::ResponsiveImage;
+use crate::data::slug::slugify;
 use crate::Route;
 use dioxus::prelude::*;
 
@@ -6,29 +9,55 @@ pub fn Card(
     title: String,
     description: String,
     image_url: String,
+    /// Build-time variants of `image_url`, if it is a local image.
+    image: Option<ResponsiveImage>,
     tags: Vec<String>,
     link_to: Option<Route>,
     external_link: Option<String>,
     link_text: Option<String>,
+    draft: Option<bool>,
+    /// Number of comments, shown when the comment backend knows it.
+    comments: Option<usize>,
 ) -> Element {
     let link_label = link_text.unwrap_or_else(|| "Read More".to_string());
 
     let content = rsx! {
-        div {
-            class: "w-full aspect-video bg-cover bg-center rounded-t-lg",
-            style: "background-image: url('{image_url}')",
+        div { class: "relative w-full aspect-video overflow-hidden rounded-t-lg",
+            Picture {
+                src: image_url,
+                alt: title.clone(),
+                image,
+                sizes: CARD_IMAGE_SIZES,
+                class: "absolute inset-0 w-full h-full object-cover",
+            }
+            if draft.unwrap_or(false) {
+                DraftBadge { class: "absolute top-3 left-3 shadow-md" }
+            }
         }
         div { class: "p-6 flex flex-col flex-grow",
             h3 { class: "text-text-dark dark:text-white text-xl font-bold leading-tight tracking-[-0.015em] group-hover:text-primary-light transition-colors",
-                "{title}"
+                if let Some(target) = link_to.clone() {
+                    // Stretched over the whole card, so the tag links can sit on top instead of nesting
+                    Link { to: target, class: "after:absolute after:inset-0", "{title}" }
+                } else {
+                    "{title}"
+                }
             }
             p { class: "text-text-dark/70 dark:text-[#D4D4D4] text-base font-normal leading-normal mt-2 mb-4 flex-grow",
                 "{description}"
             }
-            div { class: "flex flex-wrap items-center gap-2 mb-4",
+            div { class: "relative z-10 flex flex-wrap items-center gap-2 mb-4",
                 for tag in tags {
                     Badge { text: tag }
                 }
+                if let Some(count) = comments {
+                    span {
+                        class: "ml-auto flex items-center gap-1 text-xs text-text-dark/60 dark:text-gray-400",
+                        title: if count == 1 { "1 comment".to_string() } else { format!("{} comments", count) },
+                        span { class: "material-symbols-outlined text-base", "chat_bubble" }
+                        "{count}"
+                    }
+                }
             }
             if link_to.is_some() {
                 div { class: "text-primary-light text-sm font-semibold hover:underline flex items-center gap-1",
@@ -46,28 +75,42 @@ pub fn Card(
         }
     };
 
-    if let Some(target) = link_to.clone() {
+    rsx! {
+        div { class: "relative flex flex-col rounded-lg overflow-hidden bg-white dark:bg-[#2a2a2a] border border-text-dark/5 dark:border-white/10 group transition-all duration-300 hover:shadow-lg dark:hover:bg-white/5",
+            {content}
+        }
+    }
+}
+
+/// A tag label, linking to its tag page unless `link` is `false`.
+#[component]
+pub fn Badge(text: String, link: Option<bool>) -> Element {
+    let class = "text-xs font-mono bg-text-dark/5 dark:bg-gray-700/50 text-text-dark/70 dark:text-gray-300 px-2 py-1 rounded transition-colors";
+
+    if link.unwrap_or(true) {
         rsx! {
             Link {
-                to: target,
-                class: "flex flex-col rounded-lg overflow-hidden bg-white dark:bg-[#2a2a2a] border border-text-dark/5 dark:border-white/10 group transition-all duration-300 hover:shadow-lg dark:hover:bg-white/5",
-                {content}
+                class: "{class} hover:bg-primary-light/20 hover:text-primary-light",
+                to: Route::TagPage {
+                    tag: slugify(&text),
+                },
+                "{text}"
             }
         }
     } else {
         rsx! {
-            div { class: "flex flex-col rounded-lg overflow-hidden bg-white dark:bg-[#2a2a2a] border border-text-dark/5 dark:border-white/10 group transition-all duration-300 hover:shadow-lg dark:hover:bg-white/5",
-                {content}
-            }
+            span { class: "{class}", "{text}" }
         }
     }
 }
 
 #[component]
-pub fn Badge(text: String) -> Element {
+pub fn DraftBadge(class: Option<String>) -> Element {
+    let extra = class.unwrap_or_default();
     rsx! {
-        span { class: "text-xs font-mono bg-text-dark/5 dark:bg-gray-700/50 text-text-dark/70 dark:text-gray-300 px-2 py-1 rounded transition-colors",
-            "{text}"
+        span { class: "inline-flex items-center gap-1 text-xs font-bold uppercase tracking-wider bg-amber-400 text-text-dark px-2 py-1 rounded {extra}",
+            span { class: "material-symbols-outlined text-sm", "edit_note" }
+            "Draft"
         }
     }
 }
@@ -6,8 +6,9 @@ pub fn PrimaryButton(
     text: String,
     to: Option<Route>,
     onclick: Option<EventHandler<MouseEvent>>,
+    disabled: Option<bool>,
 ) -> Element {
-    let class = "flex min-w-[84px] max-w-[480px] cursor-pointer items-center justify-center overflow-hidden rounded-lg h-12 px-6 bg-primary-light text-text-dark text-base font-bold leading-normal tracking-[0.015em] hover:opacity-90 transition-all active:scale-95 shadow-md hover:shadow-lg";
+    let class = "flex min-w-[84px] max-w-[480px] cursor-pointer items-center justify-center overflow-hidden rounded-lg h-12 px-6 bg-primary-light text-text-dark text-base font-bold leading-normal tracking-[0.015em] hover:opacity-90 transition-all active:scale-95 shadow-md hover:shadow-lg disabled:opacity-60 disabled:cursor-wait disabled:active:scale-100";
 
     if let Some(route) = to.clone() {
         rsx! {
@@ -19,6 +20,7 @@ pub fn PrimaryButton(
         rsx! {
             button {
                 class: "{class}",
+                disabled: disabled.unwrap_or(false),
                 onclick: move |e| {
                     if let Some(handler) = onclick {
                         handler.call(e);
@@ -1,3 +1,7 @@
+use crate::components::{DraftBadge, Picture};
+use crate::data::date::Date;
+use crate::data::responsive::{ResponsiveImage, ARTICLE_IMAGE_SIZES};
+use crate::data::utils::format_date;
 use crate::Route;
 use dioxus::prelude::*;
 
@@ -36,10 +40,14 @@ pub fn Hero(
 pub fn DetailHero(
     title: String,
     author: String,
-    date: String,
+    date: Date,
     read_time: String,
     back_link: Route,
     back_label: String,
+    draft: Option<bool>,
+    /// Cover image shown below the byline.
+    image_url: Option<String>,
+    image: Option<ResponsiveImage>,
 ) -> Element {
     rsx! {
         header { class: "flex flex-col gap-6 border-b border-text-dark/5 dark:border-white/5 pb-10",
@@ -52,16 +60,30 @@ pub fn DetailHero(
                 span { "›" }
                 span { class: "truncate text-text-dark/40 dark:text-gray-400", "{title}" }
             }
+            if draft.unwrap_or(false) {
+                div {
+                    DraftBadge {}
+                }
+            }
             h1 { class: "text-text-dark dark:text-white text-4xl sm:text-5xl font-extrabold leading-tight tracking-tight",
                 "{title}"
             }
             div { class: "flex items-center gap-4 text-sm text-gray-400",
                 span { class: "font-medium text-primary-light", "{author}" }
                 span { class: "text-gray-600", "•" }
-                time { "{date}" }
+                time { datetime: "{date}", "{format_date(&date)}" }
                 span { class: "text-gray-600", "•" }
                 span { "{read_time}" }
             }
+            if let Some(src) = image_url {
+                Picture {
+                    src,
+                    alt: title.clone(),
+                    image,
+                    sizes: ARTICLE_IMAGE_SIZES,
+                    class: "w-full h-auto rounded-lg",
+                }
+            }
         }
     }
 }
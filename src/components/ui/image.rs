@@ -0,0 +1,55 @@
+use crate::data::responsive::{ResponsiveImage, IMAGE_FORMATS};
+use crate::data::utils::get_base_path;
+use dioxus::prelude::*;
+
+/// `sizes` for gallery cards, laid out in one, two or three columns.
+pub const CARD_IMAGE_SIZES: &str = "(min-width: 1024px) 33vw, (min-width: 768px) 50vw, 100vw";
+
+/// An image that uses the build-time variants when there are any: AVIF/WebP
+/// `srcset`s, explicit dimensions against layout shift and a blurred placeholder.
+#[component]
+pub fn Picture(
+    src: String,
+    alt: String,
+    image: Option<ResponsiveImage>,
+    sizes: String,
+    class: Option<String>,
+) -> Element {
+    let class = class.unwrap_or_default();
+
+    match image {
+        Some(image) => {
+            let base_path = get_base_path();
+            rsx! {
+                picture {
+                    for (ext , mime) in IMAGE_FORMATS {
+                        source {
+                            r#type: *mime,
+                            "srcset": image.srcset(&base_path, ext),
+                            "sizes": "{sizes}",
+                        }
+                    }
+                    img {
+                        class: "{class}",
+                        src: "{src}",
+                        alt: "{alt}",
+                        width: image.width as usize,
+                        height: image.height as usize,
+                        loading: "lazy",
+                        decoding: "async",
+                        style: image.placeholder_style(),
+                    }
+                }
+            }
+        }
+        None => rsx! {
+            img {
+                class: "{class}",
+                src: "{src}",
+                alt: "{alt}",
+                loading: "lazy",
+                decoding: "async",
+            }
+        },
+    }
+}
@@ -2,10 +2,12 @@ pub mod cards;
 pub mod display;
 pub mod forms;
 pub mod hero;
+pub mod image;
 pub mod layout;
 
 pub use cards::*;
 pub use display::*;
 pub use forms::*;
 pub use hero::*;
+pub use image::*;
 pub use layout::*;
@@ -0,0 +1,221 @@
+//! Comment backends.
+//!
+//! [`CommentProvider`] is what the comment section and the cards talk to.
+//! [`StaticComments`] serves the `comments.json` files committed next to each
+//! entry, as rendered by the build script, and [`ApiComments`] a JSON API at `COMMENTS_API_URL`; [`provider`]
+//! picks one from the site configuration.
+
+use crate::data::comment::{
+    rendered_comments_file, CommentCounts, CommentRecord, NewComment, COUNTS_PATH,
+};
+use crate::data::constants::{COMMENTS_API_URL, COMMENT_POLICY};
+use crate::data::html::{parse_fragment, Node};
+use crate::data::http::{self, HttpError};
+#[cfg(feature = "runtime-markdown")]
+use crate::data::markdown::render_comment;
+use crate::data::sanitize::sanitize;
+use crate::data::utils::fetch_json;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Why a backend could not do what was asked.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CommentError {
+    /// The backend does not take new comments.
+    ReadOnly,
+    /// The submitted comment was refused before sending, e.g. without a name.
+    Invalid(&'static str),
+    /// The request failed or the backend answered with an error.
+    Request(String),
+}
+
+impl fmt::Display for CommentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommentError::ReadOnly => write!(f, "This site does not accept new comments."),
+            CommentError::Invalid(problem) => write!(f, "{}", problem),
+            CommentError::Request(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl From<HttpError> for CommentError {
+    fn from(error: HttpError) -> Self {
+        CommentError::Request(match error {
+            // Native builds (desktop, prerender) have no fetch; the browser loads comments after hydrating
+            HttpError::Unsupported => "Comments are only available in the browser.".into(),
+            HttpError::Unreachable => "The comment server is unreachable.".into(),
+            // Servers may explain a refusal in the body, e.g. a rate limit
+            HttpError::Status(status, text) if text.trim().is_empty() => {
+                format!("The comment server answered {}.", status)
+            }
+            HttpError::Status(_, text) => text,
+            HttpError::Unexpected(e) => format!("Unexpected answer: {}", e),
+        })
+    }
+}
+
+/// Loads and stores the comments of entries.
+pub trait CommentProvider {
+    /// Whether visitors can post through this backend.
+    fn accepts_comments(&self) -> bool;
+
+    /// An entry's comments in the order they were written.
+    async fn load(&self, collection: &str, id: &str) -> Result<Vec<CommentRecord>, CommentError>;
+
+    /// Comment counts of every entry in a collection that has comments.
+    async fn counts(&self, collection: &str) -> Result<CommentCounts, CommentError>;
+
+    /// Adds a comment to an entry, returning it as stored.
+    async fn post(
+        &self,
+        collection: &str,
+        id: &str,
+        comment: &NewComment,
+    ) -> Result<CommentRecord, CommentError>;
+}
+
+/// Comments committed to the repository as `content/{collection}/{id}/comments.json`,
+/// served with their bodies rendered and with counts from the `comment_counts.json`
+/// the build script writes.
+/// New comments arrive by pull request, so posting is not supported.
+pub struct StaticComments;
+
+impl CommentProvider for StaticComments {
+    fn accepts_comments(&self) -> bool {
+        false
+    }
+
+    async fn load(&self, collection: &str, id: &str) -> Result<Vec<CommentRecord>, CommentError> {
+        // Most entries have no comments file at all
+        Ok(fetch_json(&rendered_comments_file(collection, id))
+            .await
+            .unwrap_or_default())
+    }
+
+    async fn counts(&self, collection: &str) -> Result<CommentCounts, CommentError> {
+        let mut counts: HashMap<String, CommentCounts> =
+            fetch_json(COUNTS_PATH).await.unwrap_or_default();
+        Ok(counts.remove(collection).unwrap_or_default())
+    }
+
+    async fn post(
+        &self,
+        _collection: &str,
+        _id: &str,
+        _comment: &NewComment,
+    ) -> Result<CommentRecord, CommentError> {
+        Err(CommentError::ReadOnly)
+    }
+}
+
+/// Comments kept by an HTTP JSON API, such as a small serverless function.
+pub struct ApiComments {
+    /// Base URL of the API, without a trailing slash.
+    pub url: &'static str,
+}
+
+impl CommentProvider for ApiComments {
+    fn accepts_comments(&self) -> bool {
+        true
+    }
+
+    async fn load(&self, collection: &str, id: &str) -> Result<Vec<CommentRecord>, CommentError> {
+        Ok(http::get_json(&format!("{}/{}/{}", self.url, collection, id)).await?)
+    }
+
+    async fn counts(&self, collection: &str) -> Result<CommentCounts, CommentError> {
+        Ok(http::get_json(&format!("{}/{}", self.url, collection)).await?)
+    }
+
+    async fn post(
+        &self,
+        collection: &str,
+        id: &str,
+        comment: &NewComment,
+    ) -> Result<CommentRecord, CommentError> {
+        if let Some(problem) = comment.problem() {
+            return Err(CommentError::Invalid(problem));
+        }
+        Ok(http::post_json(&format!("{}/{}/{}", self.url, collection, id), comment).await?)
+    }
+}
+
+/// The backend configured in `constants.rs`.
+pub enum Provider {
+    Static(StaticComments),
+    Api(ApiComments),
+}
+
+/// The comment backend selected by `COMMENTS_API_URL`.
+pub fn provider() -> Provider {
+    match COMMENTS_API_URL {
+        Some(url) => Provider::Api(ApiComments {
+            url: url.trim_end_matches('/'),
+        }),
+        None => Provider::Static(StaticComments),
+    }
+}
+
+impl CommentProvider for Provider {
+    fn accepts_comments(&self) -> bool {
+        match self {
+            Provider::Static(provider) => provider.accepts_comments(),
+            Provider::Api(provider) => provider.accepts_comments(),
+        }
+    }
+
+    async fn load(&self, collection: &str, id: &str) -> Result<Vec<CommentRecord>, CommentError> {
+        match self {
+            Provider::Static(provider) => provider.load(collection, id).await,
+            Provider::Api(provider) => provider.load(collection, id).await,
+        }
+    }
+
+    async fn counts(&self, collection: &str) -> Result<CommentCounts, CommentError> {
+        match self {
+            Provider::Static(provider) => provider.counts(collection).await,
+            Provider::Api(provider) => provider.counts(collection).await,
+        }
+    }
+
+    async fn post(
+        &self,
+        collection: &str,
+        id: &str,
+        comment: &NewComment,
+    ) -> Result<CommentRecord, CommentError> {
+        match self {
+            Provider::Static(provider) => provider.post(collection, id, comment).await,
+            Provider::Api(provider) => provider.post(collection, id, comment).await,
+        }
+    }
+}
+
+/// A comment's body, keeping only what `COMMENT_POLICY` allows. Static comments
+/// and APIs that send `html` arrive rendered; other bodies are rendered here with
+/// `runtime-markdown` and shown as plain paragraphs without it.
+pub fn comment_body(comment: &CommentRecord) -> Vec<Node> {
+    let html = match &comment.html {
+        Some(html) => html.clone(),
+        #[cfg(feature = "runtime-markdown")]
+        None => render_comment(&comment.body, &COMMENT_POLICY),
+        #[cfg(not(feature = "runtime-markdown"))]
+        None => return plain_text(&comment.body),
+    };
+    sanitize(parse_fragment(&html), &COMMENT_POLICY)
+}
+
+/// Text split into paragraphs at blank lines.
+#[cfg(not(feature = "runtime-markdown"))]
+fn plain_text(text: &str) -> Vec<Node> {
+    text.split("\n\n")
+        .map(str::trim)
+        .filter(|paragraph| !paragraph.is_empty())
+        .map(|paragraph| Node::Element {
+            tag: "p".to_string(),
+            attrs: Vec::new(),
+            children: vec![Node::Text(paragraph.to_string())],
+        })
+        .collect()
+}
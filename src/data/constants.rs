@@ -1,14 +1,105 @@
-/// Global site configuration constants.
-/// Update these values to customize your blog.
+//! Global site configuration constants.
+//! Update these values to customize your blog.
+//!
+//! The build script (feeds, dates, collections) and the example servers include
+//! this file with `#[path]`, so keep it to plain constants and `content_model` types.
+
+use content_model::collection::{Collection, Field, FieldKind};
+use content_model::sanitize::{self, Policy};
 
 pub const APP_TITLE: &str = "Dioxus Blog Template";
 pub const APP_SUBTITLE: &str = "A clean, high-performance blog template built with Dioxus and Rust. Perfect for documenting your technical journey.";
 pub const AUTHOR_NAME: &str = "Your Name";
 pub const AUTHOR_EMAIL: &str = "hello@example.com";
+/// Where the contact form sends messages. `None` opens the visitor's mail app
+/// addressed to `AUTHOR_EMAIL`; a URL POSTs them to a form service such as
+/// Formspree (`Some("https://formspree.io/f/yourid")`) or a function of your own.
+/// `cargo run --example contact_server` accepts messages on `http://localhost:8788` and prints them.
+pub const CONTACT_FORM_URL: Option<&str> = None;
+/// Whether POSTed messages are form-encoded, as a plain HTML form would send them, instead of JSON.
+pub const CONTACT_FORM_URLENCODED: bool = false;
+/// Only logs messages to the browser console instead of delivering them, for trying the form out.
+pub const CONTACT_FORM_DRY_RUN: bool = false;
 pub const GITHUB_USERNAME: &str = "yourusername";
 pub const GITHUB_URL: &str = "https://github.com/yourusername";
 pub const LINKEDIN_URL: &str = "https://linkedin.com/in/yourusername";
 pub const COPYRIGHT_YEAR: &str = "2026";
 
-use dioxus::prelude::*;
-pub const FAVICON: Asset = asset!("assets/favicon.png");
+/// Public origin of the deployed site, without the repository base path.
+/// Feeds combine it with `base_path` from `Dioxus.toml`; the `SITE_URL` env var overrides it at build time.
+pub const SITE_URL: &str = "https://yourusername.github.io";
+/// Whether feed entries carry the full rendered post (`true`) or only its description.
+pub const FEED_FULL_CONTENT: bool = true;
+
+/// UTC offset of the site's timezone in minutes (e.g. `540` for KST, `-300` for EST).
+/// Frontmatter times with another offset, scheduled publishing and relative dates use it.
+pub const TIMEZONE_OFFSET_MINUTES: i32 = 0;
+/// How dates are displayed: `%Y`, `%m`, `%d`, `%-m`, `%-d`, `%B` (month name) and `%b` (short month name).
+/// For Korean, pair `"%Y년 %-m월 %-d일"` with the `"ko"` locale.
+pub const DATE_FORMAT: &str = "%B %-d, %Y";
+/// Language of month names and relative dates ("3 days ago"): `"en"` or `"ko"`.
+pub const DATE_LOCALE: &str = "en";
+
+/// Content collections, each a folder of `public/content/{name}/{id}/index.md`
+/// entries served at `/{route}` and `/{route}/{id}`. The first one is linked
+/// from the home page. Route segments must not clash with the app's own pages
+/// (`about`, `contact`, `series`, `tags`).
+pub const COLLECTIONS: &[Collection] = &[
+    Collection {
+        name: "posts",
+        singular: "post",
+        route: "blog",
+        label: "Blog",
+        title: "The Journey's Log",
+        subtitle: "Documenting every breakthrough and lesson learned while navigating the Rust ecosystem—from bare-metal firmware to cloud-native services.",
+        item: "Post",
+        search_placeholder: "Search articles...",
+        fields: &[],
+        feed: true,
+        share: true,
+        call_to_action: false,
+    },
+    Collection {
+        name: "projects",
+        singular: "project",
+        route: "projects",
+        label: "Projects",
+        title: "The Workshop",
+        subtitle: "Tangible milestones of my journey—a curated collection of tools, libraries, and applications forged along the road.",
+        item: "Project",
+        search_placeholder: "Search projects...",
+        fields: &[
+            Field {
+                name: "link",
+                kind: FieldKind::Link {
+                    text_field: Some("link_text"),
+                },
+                required: false,
+                label: Some("Visit Project"),
+            },
+            Field {
+                name: "link_text",
+                kind: FieldKind::Text,
+                required: false,
+                label: None,
+            },
+        ],
+        feed: false,
+        share: false,
+        call_to_action: true,
+    },
+];
+
+/// HTML allowed in rendered entry bodies; anything else is removed at render time.
+/// Start from the defaults in `sanitize.rs` and override fields to loosen or tighten it,
+/// e.g. `Policy { url_schemes: &["https"], ..sanitize::CONTENT }`.
+pub const CONTENT_POLICY: Policy = sanitize::CONTENT;
+/// HTML allowed in text written by visitors, such as comments.
+pub const COMMENT_POLICY: Policy = sanitize::COMMENTS;
+
+/// Where comments are kept. `None` serves the `comments.json` files committed
+/// next to each `index.md` (read-only); a URL sends comments to an HTTP JSON API:
+/// `GET {url}/{collection}/{id}` lists an entry's comments, `POST` to the same
+/// URL adds one and `GET {url}/{collection}` returns comment counts by entry id.
+/// `cargo run --example comments_server` serves such an API on `http://localhost:8787`.
+pub const COMMENTS_API_URL: Option<&str> = None;
@@ -0,0 +1,301 @@
+//! Contact form delivery.
+//!
+//! [`ContactBackend`] is what the contact page sends messages through.
+//! [`PostBackend`] POSTs them to a form endpoint, [`MailtoBackend`] hands
+//! them to the visitor's mail app and [`DryRunBackend`] only logs them;
+//! [`backend`] picks one from the `CONTACT_FORM_*` settings.
+
+use crate::data::constants::{
+    APP_TITLE, AUTHOR_EMAIL, CONTACT_FORM_DRY_RUN, CONTACT_FORM_URL, CONTACT_FORM_URLENCODED,
+};
+use crate::data::http::{self, HttpError};
+use dioxus::logger::tracing;
+use dioxus::prelude::document;
+use serde_json::json;
+
+/// How a POSTed message is encoded.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Encoding {
+    Json,
+    /// `application/x-www-form-urlencoded`, as a plain HTML form would send it.
+    Form,
+}
+
+impl Encoding {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Encoding::Json => "application/json",
+            Encoding::Form => "application/x-www-form-urlencoded",
+        }
+    }
+}
+
+/// A message written in the contact form.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ContactMessage {
+    pub name: String,
+    pub email: String,
+    pub message: String,
+}
+
+impl ContactMessage {
+    /// What keeps the message from being sent, if anything.
+    pub fn problem(&self) -> Option<&'static str> {
+        let email = self.email.trim();
+        let valid_email = email
+            .split_once('@')
+            .is_some_and(|(user, domain)| !user.is_empty() && domain.contains('.'))
+            && !email.contains(char::is_whitespace);
+        if self.name.trim().is_empty() {
+            Some("Please enter your name.")
+        } else if !valid_email {
+            Some("Please enter a valid email address.")
+        } else if self.message.trim().is_empty() {
+            Some("Please write a message.")
+        } else {
+            None
+        }
+    }
+
+    pub fn subject(&self) -> String {
+        format!("Message from {} via {}", self.name.trim(), APP_TITLE)
+    }
+
+    /// The request body of a [`PostBackend`].
+    pub fn encode(&self, encoding: Encoding) -> String {
+        let subject = self.subject();
+        let fields = [
+            ("name", self.name.trim()),
+            ("email", self.email.trim()),
+            ("subject", subject.as_str()),
+            ("message", self.message.trim()),
+        ];
+        match encoding {
+            Encoding::Json => {
+                let object: serde_json::Map<String, serde_json::Value> = fields
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), json!(value)))
+                    .collect();
+                serde_json::Value::Object(object).to_string()
+            }
+            Encoding::Form => fields
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, percent_encode(value, true)))
+                .collect::<Vec<_>>()
+                .join("&"),
+        }
+    }
+
+    /// A `mailto:` link to `to` with the subject and body filled in.
+    pub fn mailto_url(&self, to: &str) -> String {
+        let body = format!(
+            "{}\n\n{} <{}>",
+            self.message.trim(),
+            self.name.trim(),
+            self.email.trim()
+        )
+        // Mail clients expect CRLF line breaks in a `mailto:` body
+        .replace("\r\n", "\n")
+        .replace('\n', "\r\n");
+        format!(
+            "mailto:{}?subject={}&body={}",
+            to,
+            percent_encode(&self.subject(), false),
+            percent_encode(&body, false)
+        )
+    }
+}
+
+/// Percent-encodes everything but unreserved characters, writing spaces as
+/// `+` in form bodies and `%20` elsewhere.
+fn percent_encode(text: &str, form: bool) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b' ' if form => encoded.push('+'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// What happened to a message that was not refused.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Delivery {
+    /// The endpoint accepted it.
+    Sent,
+    /// The visitor's mail app was opened with it; they still have to send it.
+    MailApp,
+    /// It was only logged.
+    Logged,
+}
+
+/// Delivers contact form messages. Errors are a sentence to show the visitor.
+pub trait ContactBackend {
+    async fn send(&self, message: &ContactMessage) -> Result<Delivery, String>;
+}
+
+/// POSTs messages to a form service or function, JSON or form-encoded.
+pub struct PostBackend {
+    pub url: &'static str,
+    pub encoding: Encoding,
+}
+
+impl ContactBackend for PostBackend {
+    async fn send(&self, message: &ContactMessage) -> Result<Delivery, String> {
+        let body = message.encode(self.encoding);
+        http::post(self.url, self.encoding.content_type(), body)
+            .await
+            .map_err(error_message)?;
+        Ok(Delivery::Sent)
+    }
+}
+
+/// What to tell the visitor about a failed POST: the `error` field of a JSON
+/// answer, as form services send it, or the answer's text.
+fn error_message(error: HttpError) -> String {
+    match error {
+        HttpError::Unsupported => "Messages can only be sent from the browser.".to_string(),
+        HttpError::Unreachable => "The form service could not be reached.".to_string(),
+        HttpError::Status(status, text) => {
+            let from_json = serde_json::from_str::<serde_json::Value>(&text)
+                .ok()
+                .and_then(|value| value.get("error")?.as_str().map(str::to_string));
+            match from_json {
+                Some(message) => message,
+                None if text.trim().is_empty() => {
+                    format!("The form service answered {}.", status)
+                }
+                None => text.trim().to_string(),
+            }
+        }
+        HttpError::Unexpected(e) => format!("Unexpected answer: {}", e),
+    }
+}
+
+/// Opens a `mailto:` link with the subject and body filled in.
+pub struct MailtoBackend {
+    pub to: &'static str,
+}
+
+impl ContactBackend for MailtoBackend {
+    async fn send(&self, message: &ContactMessage) -> Result<Delivery, String> {
+        // Through the document so desktop webviews open the mail app too
+        let url = serde_json::to_string(&message.mailto_url(self.to)).unwrap();
+        document::eval(&format!("window.location.href = {};", url));
+        Ok(Delivery::MailApp)
+    }
+}
+
+/// Logs messages instead of sending them, for trying the form out.
+pub struct DryRunBackend;
+
+impl ContactBackend for DryRunBackend {
+    async fn send(&self, message: &ContactMessage) -> Result<Delivery, String> {
+        tracing::info!(
+            "contact form (dry run): {}\n{}",
+            message.subject(),
+            message.encode(Encoding::Json)
+        );
+        Ok(Delivery::Logged)
+    }
+}
+
+/// The backend configured in `constants.rs`.
+pub enum Backend {
+    Post(PostBackend),
+    Mailto(MailtoBackend),
+    DryRun(DryRunBackend),
+}
+
+/// The contact backend selected by `CONTACT_FORM_DRY_RUN` and `CONTACT_FORM_URL`.
+pub fn backend() -> Backend {
+    let encoding = if CONTACT_FORM_URLENCODED {
+        Encoding::Form
+    } else {
+        Encoding::Json
+    };
+    match CONTACT_FORM_URL {
+        _ if CONTACT_FORM_DRY_RUN => Backend::DryRun(DryRunBackend),
+        Some(url) => Backend::Post(PostBackend { url, encoding }),
+        None => Backend::Mailto(MailtoBackend { to: AUTHOR_EMAIL }),
+    }
+}
+
+impl ContactBackend for Backend {
+    async fn send(&self, message: &ContactMessage) -> Result<Delivery, String> {
+        // Checked here so every backend refuses the same incomplete messages
+        if let Some(problem) = message.problem() {
+            return Err(problem.to_string());
+        }
+        match self {
+            Backend::Post(backend) => backend.send(message).await,
+            Backend::Mailto(backend) => backend.send(message).await,
+            Backend::DryRun(backend) => backend.send(message).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message() -> ContactMessage {
+        ContactMessage {
+            name: " Jane Cooper ".to_string(),
+            email: "jane@example.com".to_string(),
+            message: "Hi & hello,\nsee you!".to_string(),
+        }
+    }
+
+    #[test]
+    fn requires_name_email_and_message() {
+        assert_eq!(message().problem(), None);
+        for (email, valid) in [
+            ("jane@example.com", true),
+            ("jane@localhost", false),
+            ("@example.com", false),
+            ("jane doe@example.com", false),
+            ("", false),
+        ] {
+            let message = ContactMessage {
+                email: email.to_string(),
+                ..message()
+            };
+            assert_eq!(message.problem().is_none(), valid, "{}", email);
+        }
+        let blank = ContactMessage {
+            message: "  ".to_string(),
+            ..message()
+        };
+        assert_eq!(blank.problem(), Some("Please write a message."));
+    }
+
+    #[test]
+    fn encodes_form_and_json_bodies() {
+        let form = message().encode(Encoding::Form);
+        assert!(form.starts_with(
+            "name=Jane+Cooper&email=jane%40example.com&subject=Message+from+Jane+Cooper+via+"
+        ));
+        assert!(form.ends_with("&message=Hi+%26+hello%2C%0Asee+you%21"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&message().encode(Encoding::Json)).unwrap();
+        assert_eq!(json["name"], "Jane Cooper");
+        assert_eq!(json["message"], "Hi & hello,\nsee you!");
+    }
+
+    #[test]
+    fn fills_in_mailto_links() {
+        let url = message().mailto_url("hello@example.com");
+        assert!(url.starts_with(
+            "mailto:hello@example.com?subject=Message%20from%20Jane%20Cooper%20via%20"
+        ));
+        assert!(url.ends_with(
+            "&body=Hi%20%26%20hello%2C%0D%0Asee%20you%21%0D%0A%0D%0AJane%20Cooper%20%3Cjane%40example.com%3E"
+        ));
+    }
+}
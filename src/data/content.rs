@@ -0,0 +1,110 @@
+use crate::data::collection::{Collection, EntryMeta};
+use crate::data::constants::COLLECTIONS;
+#[cfg(feature = "runtime-markdown")]
+use crate::data::constants::TIMEZONE_OFFSET_MINUTES;
+#[cfg(feature = "runtime-markdown")]
+use crate::data::frontmatter::split_frontmatter;
+use crate::data::rendered::RenderedContent;
+use crate::data::series::Series;
+use crate::data::utils::{fetch_json, fetch_rendered};
+#[cfg(feature = "runtime-markdown")]
+use crate::data::utils::{fetch_text, render_content};
+#[cfg(feature = "runtime-markdown")]
+use crate::data::wikilink::LinkTargets;
+
+/// A complete entry with its body pre-rendered to HTML by the build script.
+pub type Entry = RenderedContent<EntryMeta>;
+
+/// Fetches the metadata of every entry in a collection, sorted by date descending.
+pub async fn fetch_entries(collection: &Collection) -> Vec<EntryMeta> {
+    let mut entries: Vec<EntryMeta> =
+        fetch_json(&format!("content/{}_index.json", collection.name))
+            .await
+            .unwrap_or_default();
+    // Sort by date descending
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.date));
+    entries
+}
+
+/// Fetches the entries of every collection, in `COLLECTIONS` order.
+pub async fn fetch_all_collections() -> Vec<(&'static Collection, Vec<EntryMeta>)> {
+    let mut all = Vec::new();
+    for collection in COLLECTIONS {
+        all.push((collection, fetch_entries(collection).await));
+    }
+    all
+}
+
+/// Derives unique categories from a list of entries.
+pub fn derive_categories(entries: &[EntryMeta]) -> Vec<String> {
+    let mut categories = std::collections::HashSet::new();
+    for entry in entries.iter().filter(|e| e.status.is_listed()) {
+        for tag in &entry.tags {
+            categories.insert(tag.clone());
+        }
+    }
+    let mut categories: Vec<String> = categories.into_iter().collect();
+    categories.sort();
+    categories
+}
+
+/// Fetches every series with its parts in reading order.
+pub async fn fetch_all_series() -> Vec<Series> {
+    fetch_json("content/series_index.json")
+        .await
+        .unwrap_or_default()
+}
+
+/// Fetches a series by its ID.
+pub async fn get_series_by_id(id: &str) -> Option<Series> {
+    fetch_all_series()
+        .await
+        .into_iter()
+        .find(|series| series.id == id)
+}
+
+/// Fetches the series an entry belongs to, if any.
+pub async fn get_series_for_entry(collection: &str, id: &str) -> Option<Series> {
+    fetch_all_series()
+        .await
+        .into_iter()
+        .find(|series| series.position(collection, id).is_some())
+}
+
+/// Fetches a specific entry by its ID, preferring the build-time rendering.
+pub async fn get_entry_by_id(collection: &Collection, id: &str) -> Option<Entry> {
+    match fetch_rendered(collection.name, id).await {
+        Some(entry) => Some(entry),
+        None => fetch_markdown_entry(collection, id).await,
+    }
+}
+
+/// Renders `index.md` in the browser when no pre-rendered file exists.
+#[cfg(feature = "runtime-markdown")]
+async fn fetch_markdown_entry(collection: &Collection, id: &str) -> Option<Entry> {
+    let content = fetch_text(&format!("content/{}/{}/index.md", collection.name, id)).await?;
+
+    let frontmatter = split_frontmatter(&content)?;
+    let meta = EntryMeta::parse(&frontmatter, collection, id, TIMEZONE_OFFSET_MINUTES).ok()?;
+
+    // Wiki links resolve against every collection's index
+    let mut targets = LinkTargets::new(COLLECTIONS);
+    for (collection, entries) in fetch_all_collections().await {
+        for entry in entries {
+            targets.push(collection, &entry.id, &entry.title);
+        }
+    }
+
+    Some(render_content(
+        meta,
+        id,
+        frontmatter.body.trim(),
+        collection,
+        &targets,
+    ))
+}
+
+#[cfg(not(feature = "runtime-markdown"))]
+async fn fetch_markdown_entry(_collection: &Collection, _id: &str) -> Option<Entry> {
+    None
+}
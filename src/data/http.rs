@@ -0,0 +1,97 @@
+//! Requests to servers other than the site itself, for the comment and
+//! contact form backends.
+//!
+//! Only the browser can make these requests; in desktop builds every one of
+//! them fails with [`HttpError::Unsupported`]. The prerenderer leaves them
+//! pending, as they are in the first render of the page it hydrates. Each
+//! backend words the errors for its own visitors.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Why a request failed.
+#[derive(Clone, Debug, PartialEq)]
+// Native builds never get as far as a response
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+pub enum HttpError {
+    /// Requests can only be made from the browser.
+    Unsupported,
+    /// The server could not be reached at all.
+    Unreachable,
+    /// The server answered with an error status, and this text.
+    Status(u16, String),
+    /// The answer was not the JSON that was expected.
+    Unexpected(String),
+}
+
+/// GETs `url` and parses the JSON answer.
+pub async fn get_json<T: DeserializeOwned>(url: &str) -> Result<T, HttpError> {
+    parse(&imp::send("GET", url, None).await?)
+}
+
+/// POSTs `body` as JSON and parses the JSON answer.
+pub async fn post_json<B: Serialize, T: DeserializeOwned>(
+    url: &str,
+    body: &B,
+) -> Result<T, HttpError> {
+    let body = serde_json::to_string(body).map_err(|e| HttpError::Unexpected(e.to_string()))?;
+    parse(&imp::send("POST", url, Some(("application/json", body))).await?)
+}
+
+/// POSTs an already encoded body, returning the answer as text.
+pub async fn post(url: &str, content_type: &str, body: String) -> Result<String, HttpError> {
+    imp::send("POST", url, Some((content_type, body))).await
+}
+
+fn parse<T: DeserializeOwned>(text: &str) -> Result<T, HttpError> {
+    serde_json::from_str(text).map_err(|e| HttpError::Unexpected(e.to_string()))
+}
+
+#[cfg(target_arch = "wasm32")]
+mod imp {
+    use super::HttpError;
+    use gloo_net::http::{Method, RequestBuilder};
+
+    pub async fn send(
+        method: &str,
+        url: &str,
+        body: Option<(&str, String)>,
+    ) -> Result<String, HttpError> {
+        let method = if method == "POST" {
+            Method::POST
+        } else {
+            Method::GET
+        };
+        // Form services answer with a redirect to a thank-you page unless asked for JSON
+        let builder = RequestBuilder::new(url)
+            .method(method)
+            .header("Accept", "application/json");
+        let request = match body {
+            Some((content_type, body)) => builder.header("Content-Type", content_type).body(body),
+            None => builder.build(),
+        }
+        .map_err(|e| HttpError::Unexpected(e.to_string()))?;
+        let response = request.send().await.map_err(|_| HttpError::Unreachable)?;
+        let text = response.text().await.unwrap_or_default();
+        if response.ok() {
+            Ok(text)
+        } else {
+            Err(HttpError::Status(response.status(), text))
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod imp {
+    use super::HttpError;
+
+    pub async fn send(
+        _method: &str,
+        _url: &str,
+        _body: Option<(&str, String)>,
+    ) -> Result<String, HttpError> {
+        #[cfg(feature = "prerender")]
+        std::future::pending::<()>().await;
+        Err(HttpError::Unsupported)
+    }
+}
@@ -1,4 +1,19 @@
-pub mod blog;
+pub use content_model::{
+    collection, comment, date, html, path, rendered, responsive, sanitize, series, shortcode, slug,
+    taxonomy,
+};
+#[cfg(feature = "runtime-markdown")]
+pub use content_model::{frontmatter, wikilink};
+#[cfg(feature = "runtime-markdown")]
+pub use content_render::markdown;
+pub mod comments;
+// Shared with the build script, which reads the feed and site URL settings
+#[allow(dead_code)]
 pub mod constants;
-pub mod projects;
+pub mod contact_form;
+pub mod content;
+pub mod http;
+pub mod preload;
+pub mod search;
+pub mod tags;
 pub mod utils;
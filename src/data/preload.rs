@@ -0,0 +1,74 @@
+//! Content files embedded into prerendered pages.
+//!
+//! While `prerender` renders a route it records every file the page loads, and
+//! the ones it found missing. They are written into the page as JSON, and the
+//! wasm app reads them back instead of fetching them, so its first render has
+//! the same content as the static HTML it hydrates.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+/// Id of the `<script type="application/json">` tag holding the embedded files.
+#[cfg(any(target_arch = "wasm32", feature = "prerender"))]
+pub const PRELOAD_SCRIPT_ID: &str = "preloaded-content";
+
+thread_local! {
+    static FILES: RefCell<Option<BTreeMap<String, Option<String>>>> = const { RefCell::new(None) };
+}
+
+/// Returns a file embedded in the current page, keyed by its path under the site root.
+/// `Some(None)` means the file did not exist when the page was prerendered.
+#[cfg(target_arch = "wasm32")]
+pub fn get(path: &str) -> Option<Option<String>> {
+    FILES.with(|files| {
+        files
+            .borrow_mut()
+            .get_or_insert_with(read_embedded)
+            .get(path)
+            .cloned()
+    })
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read_embedded() -> BTreeMap<String, Option<String>> {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(PRELOAD_SCRIPT_ID))
+        .and_then(|script| serde_json::from_str(&script.inner_html()).ok())
+        .unwrap_or_default()
+}
+
+/// Empties the mount point so the app renders in place of the prerendered markup.
+/// Without the `hydrate` feature Dioxus appends to `#main`, so leaving the static
+/// HTML would show the page twice.
+#[cfg(all(target_arch = "wasm32", not(feature = "hydrate")))]
+pub fn clear_prerendered_markup() {
+    if let Some(main) = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id("main"))
+    {
+        main.set_inner_html("");
+    }
+}
+
+/// Remembers a loaded file, or that it is missing, for the page being prerendered, if any.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn record(path: &str, content: Option<&str>) {
+    FILES.with(|files| {
+        if let Some(files) = files.borrow_mut().as_mut() {
+            files.insert(path.to_string(), content.map(str::to_string));
+        }
+    });
+}
+
+/// Starts collecting the files loaded by the next prerendered page.
+#[cfg(feature = "prerender")]
+pub fn start_recording() {
+    FILES.with(|files| *files.borrow_mut() = Some(BTreeMap::new()));
+}
+
+/// Stops collecting and returns the files loaded since [`start_recording`].
+#[cfg(feature = "prerender")]
+pub fn finish_recording() -> BTreeMap<String, Option<String>> {
+    FILES.with(|files| files.borrow_mut().take().unwrap_or_default())
+}
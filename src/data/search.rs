@@ -0,0 +1,19 @@
+use crate::data::utils::fetch_json;
+use content_model::search::SearchIndex;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+thread_local! {
+    static SEARCH_INDEX: RefCell<Option<Rc<SearchIndex>>> = const { RefCell::new(None) };
+}
+
+/// Fetches the search index on first use and caches it for later queries.
+pub async fn load_search_index() -> Option<Rc<SearchIndex>> {
+    if let Some(index) = SEARCH_INDEX.with(|cell| cell.borrow().clone()) {
+        return Some(index);
+    }
+    let index: SearchIndex = fetch_json("content/search_index.json").await?;
+    let index = Rc::new(index);
+    SEARCH_INDEX.with(|cell| *cell.borrow_mut() = Some(index.clone()));
+    Some(index)
+}
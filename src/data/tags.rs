@@ -0,0 +1,19 @@
+use crate::data::taxonomy::TagInfo;
+use crate::data::utils::fetch_json;
+
+/// Fetches every tag used by a listed entry, sorted by name.
+pub async fn fetch_all_tags() -> Vec<TagInfo> {
+    let mut tags: Vec<TagInfo> = fetch_json("content/tags_index.json")
+        .await
+        .unwrap_or_default();
+    tags.sort_by_key(|tag| tag.name.to_lowercase());
+    tags
+}
+
+/// Fetches a tag by its slug.
+pub async fn get_tag_by_slug(slug: &str) -> Option<TagInfo> {
+    fetch_all_tags()
+        .await
+        .into_iter()
+        .find(|tag| tag.slug == slug)
+}
@@ -1,5 +1,22 @@
-use pulldown_cmark::{html, Event, Options, Parser, Tag};
-use serde::{Deserialize, Serialize};
+#[cfg(feature = "runtime-markdown")]
+use crate::data::collection::Collection;
+#[cfg(feature = "runtime-markdown")]
+use crate::data::constants::CONTENT_POLICY;
+use crate::data::constants::{DATE_FORMAT, DATE_LOCALE, TIMEZONE_OFFSET_MINUTES};
+use crate::data::date::Date;
+#[cfg(feature = "runtime-markdown")]
+use crate::data::html::Node;
+#[cfg(feature = "runtime-markdown")]
+use crate::data::markdown::{extract_toc, render_document, word_count};
+use crate::data::preload;
+use crate::data::rendered::RenderedContent;
+#[cfg(feature = "runtime-markdown")]
+use crate::data::wikilink::LinkTargets;
+use serde::de::DeserializeOwned;
+
+/// Absolute URL of the deployed site root (`SITE_URL` plus `base_path`), as the
+/// build script resolved it for the feeds.
+pub const SITE_ROOT_URL: &str = env!("SITE_ROOT_URL");
 
 /// Dynamically detects the base path from the <base> tag in the HTML.
 /// This allows the same code to work in both local 'dx serve' (usually /)
@@ -18,41 +35,37 @@ pub fn get_base_path() -> String {
             }
         }
     }
+    // The prerenderer reads the same <base> tag from the bundled index.html
+    #[cfg(feature = "prerender")]
+    if let Some(base_path) = crate::prerender::base_path() {
+        return base_path;
+    }
     "".to_string()
 }
 
-#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
-pub struct CommonMeta {
-    #[serde(default)]
-    pub id: String,
-    pub title: String,
-    pub date: String,
-    pub author: String,
-    pub description: String,
-    pub image_url: String,
-    pub tags: Vec<String>,
+/// Today's date in the site timezone.
+pub fn today() -> Date {
+    #[cfg(target_arch = "wasm32")]
+    let secs = (web_sys::js_sys::Date::now() / 1000.0) as i64;
+    #[cfg(not(target_arch = "wasm32"))]
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() as i64)
+        .unwrap_or_default();
+    Date::from_unix(secs, TIMEZONE_OFFSET_MINUTES)
 }
 
-pub fn parse_frontmatter<T: for<'de> Deserialize<'de>>(content: &str) -> Result<(T, &str), String> {
-    if !content.starts_with("---") {
-        return Err("No frontmatter found".to_string());
-    }
-
-    let parts: Vec<&str> = content.splitn(3, "---").collect();
-    if parts.len() < 3 {
-        return Err("Invalid frontmatter format".to_string());
-    }
-
-    let yaml = parts[1];
-    let markdown = parts[2];
-
-    let meta: T = serde_yaml::from_str(yaml).map_err(|e| e.to_string())?;
+/// Formats a date for display with `DATE_FORMAT` and `DATE_LOCALE`.
+pub fn format_date(date: &Date) -> String {
+    date.format(DATE_FORMAT, DATE_LOCALE)
+}
 
-    Ok((meta, markdown.trim()))
+/// Formats a date relative to today, e.g. "3 days ago".
+pub fn relative_date(date: &Date) -> String {
+    date.relative(today(), DATE_LOCALE)
 }
 
-pub fn get_read_time(content: &str) -> String {
-    let words = content.split_whitespace().count();
+pub fn get_read_time(words: usize) -> String {
     let minutes = (words as f32 / 200.0).ceil() as u32;
     if minutes <= 1 {
         "1 min read".to_string()
@@ -61,79 +74,78 @@ pub fn get_read_time(content: &str) -> String {
     }
 }
 
-pub fn markdown_to_html(markdown: &str, post_id: &str, content_type: &str) -> String {
-    let mut options = Options::empty();
-    options.insert(Options::ENABLE_STRIKETHROUGH);
-    options.insert(Options::ENABLE_TABLES);
-    options.insert(Options::ENABLE_FOOTNOTES);
-    options.insert(Options::ENABLE_TASKLISTS);
-
-    let parser = Parser::new_ext(markdown, options).map(|event| match event {
-        Event::Start(Tag::Link {
-            link_type,
-            dest_url,
-            title,
-            id,
-        }) => {
-            let mut new_dest = dest_url.to_string();
-            if new_dest.starts_with('/') {
-                // Case 1: Root-relative path (e.g., "/about")
-                new_dest = format!("{}{}", get_base_path(), new_dest);
-            } else if !new_dest.starts_with("http") {
-                // Case 2: Relative path to the current directory (e.g., "next-post.md")
-                let clean_path = new_dest.trim_start_matches("./");
-                new_dest = format!(
-                    "{}/content/{}/{}/{}",
-                    get_base_path(),
-                    content_type,
-                    post_id,
-                    clean_path
-                );
-            }
-            // Case 3: External URL - leave as is
+/// Fetches the build-time rendering of an entry from `content/rendered/{content_type}/{id}.json`.
+pub async fn fetch_rendered<M: DeserializeOwned>(
+    content_type: &str,
+    id: &str,
+) -> Option<RenderedContent<M>> {
+    fetch_json(&format!("content/rendered/{}/{}.json", content_type, id)).await
+}
 
-            Event::Start(Tag::Link {
-                link_type,
-                dest_url: new_dest.into(),
-                title,
-                id,
-            })
+/// Loads a file served from the site root, e.g. `content/posts_index.json`.
+/// Files embedded by the prerenderer are used before going to the network.
+pub async fn fetch_text(path: &str) -> Option<String> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        if let Some(content) = preload::get(path) {
+            return content;
+        }
+        let url = format!("{}/{}", get_base_path(), path);
+        let resp = gloo_net::http::Request::get(&url).send().await.ok()?;
+        if !resp.ok() {
+            return None;
         }
-        Event::Start(Tag::Image {
-            link_type,
-            dest_url,
-            title,
-            id,
-        }) => {
-            let mut new_dest = dest_url.to_string();
+        resp.text().await.ok()
+    }
+    // Native builds (desktop, prerender) read the same files straight from `public/`
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let content = std::fs::read_to_string(std::path::Path::new("public").join(path)).ok();
+        preload::record(path, content.as_deref());
+        content
+    }
+}
 
-            if new_dest.starts_with('/') {
-                // Case 1: Root-relative path (e.g., "/assets/logo.png")
-                new_dest = format!("{}{}", get_base_path(), new_dest);
-            } else if !new_dest.starts_with("http") {
-                // Case 2: Relative path to the post/project folder (e.g., "thumbnail.png")
-                let clean_path = new_dest.trim_start_matches("./");
-                new_dest = format!(
-                    "{}/content/{}/{}/{}",
-                    get_base_path(),
-                    content_type,
-                    post_id,
-                    clean_path
-                );
-            }
-            // Case 3: External URL (starts with http) - leave as is
+/// Loads and deserializes a JSON file served from the site root.
+pub async fn fetch_json<T: DeserializeOwned>(path: &str) -> Option<T> {
+    serde_json::from_str(&fetch_text(path).await?).ok()
+}
 
-            Event::Start(Tag::Image {
-                link_type,
-                dest_url: new_dest.into(),
-                title,
-                id,
-            })
-        }
-        _ => event,
-    });
+/// Renders an entry in the browser, mirroring what the build script writes.
+#[cfg(feature = "runtime-markdown")]
+pub fn render_content<M>(
+    meta: M,
+    id: &str,
+    markdown: &str,
+    collection: &Collection,
+    targets: &LinkTargets,
+) -> RenderedContent<M> {
+    RenderedContent {
+        meta,
+        body: markdown_to_document(markdown, id, collection, targets),
+        toc: extract_toc(markdown),
+        word_count: word_count(markdown),
+        related: Vec::new(),
+        backlinks: Vec::new(),
+    }
+}
 
-    let mut html_output = String::new();
-    html::push_html(&mut html_output, parser);
-    html_output
+/// Renders post/project markdown for the page, resolving links and images
+/// against the runtime base path.
+#[cfg(feature = "runtime-markdown")]
+pub fn markdown_to_document(
+    markdown: &str,
+    post_id: &str,
+    collection: &Collection,
+    targets: &LinkTargets,
+) -> Vec<Node> {
+    render_document(
+        markdown,
+        post_id,
+        collection,
+        &get_base_path(),
+        &Default::default(),
+        targets,
+        &CONTENT_POLICY,
+    )
 }
@@ -1,20 +1,144 @@
 use dioxus::prelude::*;
+use std::future::Future;
 
-pub fn use_syntax_highlighting() {
+/// `use_resource` that polls its future once while the component is created, so
+/// a value that is ready right away is there on the first render. Content files
+/// always are, read from disk by the prerenderer and from the page by the app,
+/// which lets the app's first render match the prerendered markup it hydrates.
+pub fn use_eager_resource<T, F>(future: impl FnMut() -> F + 'static) -> Resource<T>
+where
+    T: 'static,
+    F: Future<Output = T> + 'static,
+{
+    let resource = use_resource(future);
+    use_hook(|| {
+        let _ = resource.task().poll_now();
+    });
+    resource
+}
+
+/// `false` during a component's first render, which has to match the
+/// prerendered markup, and `true` from then on. Output that depends on when the
+/// page is read, like relative dates, waits for it.
+pub fn use_hydrated() -> bool {
+    let mut hydrated = use_signal(|| false);
+    use_effect(move || hydrated.set(true));
+    hydrated()
+}
+
+/// Where the reader is in an article, see [`use_scroll_spy`].
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize)]
+pub struct ScrollSpy {
+    /// Id of the last body heading scrolled past, if any.
+    pub active: Option<String>,
+    /// How far down the page the reader is, from `0.0` to `1.0`.
+    pub progress: f64,
+}
+
+/// Tracks the heading being read and the reading progress as the page scrolls.
+/// Headings are looked up on every update, and again whenever the window or the
+/// `<article>` changes size, which is watched once `content` resolves.
+pub fn use_scroll_spy<T: 'static>(content: Resource<T>) -> Signal<ScrollSpy> {
+    let mut state = use_signal(ScrollSpy::default);
+    use_future(move || async move {
+        let mut eval = document::eval(
+            "
+            window.__stopScrollSpy?.();
+            let frame = 0;
+            let last = '';
+            const update = () => {
+                frame = 0;
+                let active = null;
+                for (const heading of document.querySelectorAll('.prose :is(h1, h2, h3, h4, h5, h6)[id]')) {
+                    if (heading.getBoundingClientRect().top > 120) break;
+                    active = heading.id;
+                }
+                const root = document.documentElement;
+                const scrollable = root.scrollHeight - root.clientHeight;
+                const progress = scrollable > 0 ? Math.min(1, window.scrollY / scrollable) : 0;
+                const state = { active, progress: Math.round(progress * 1000) / 1000 };
+                const key = JSON.stringify(state);
+                if (key !== last) {
+                    last = key;
+                    dioxus.send(state);
+                }
+            };
+            const schedule = () => { frame ||= requestAnimationFrame(update); };
+            const resize = new ResizeObserver(schedule);
+            window.__observeScrollSpy = () => {
+                resize.disconnect();
+                const article = document.querySelector('article');
+                if (article) resize.observe(article);
+                schedule();
+            };
+            window.addEventListener('scroll', schedule, { passive: true });
+            window.addEventListener('resize', schedule);
+            window.__stopScrollSpy = () => {
+                window.removeEventListener('scroll', schedule);
+                window.removeEventListener('resize', schedule);
+                resize.disconnect();
+                cancelAnimationFrame(frame);
+                delete window.__observeScrollSpy;
+            };
+            window.__observeScrollSpy();
+        ",
+        );
+        while let Ok(next) = eval.recv::<ScrollSpy>().await {
+            state.set(next);
+        }
+    });
+    use_effect(move || {
+        if content.read().is_some() {
+            document::eval("window.__observeScrollSpy?.();");
+        }
+    });
+    use_drop(|| {
+        document::eval("window.__stopScrollSpy?.();");
+    });
+    state
+}
+
+/// Makes links to sections of the current page scroll there instead of
+/// reloading the page (the `<base>` tag defeats plain `#id` links), copies a
+/// heading's link when its anchor is clicked, and jumps to the section named in
+/// the URL each time `content` resolves, once its body is on the page.
+pub fn use_section_links<T: 'static>(content: Resource<T>) {
     use_effect(move || {
-        document::eval("
-            const highlight = () => {
-                if (window.hljs) {
-                    document.querySelectorAll('pre code:not([data-highlighted=\"true\"])').forEach((el) => {
-                        window.hljs.highlightElement(el);
-                        el.setAttribute('data-highlighted', 'true');
+        if content.read().is_some() {
+            document::eval(
+                "
+                const wanted = decodeURIComponent(location.hash.slice(1));
+                if (wanted) document.getElementById(wanted)?.scrollIntoView();
+            ",
+            );
+        }
+    });
+    use_effect(move || {
+        document::eval(
+            "
+            window.__stopSectionLinks?.();
+            const page = (url) => url.origin + url.pathname.replace(/\\/$/, '');
+            const onClick = (event) => {
+                const link = event.target.closest('a[href*=\"#\"]');
+                if (!link || page(link) !== page(location)) return;
+                const target = document.getElementById(decodeURIComponent(link.hash.slice(1)));
+                if (!target) return;
+                event.preventDefault();
+                history.replaceState(history.state, '', link.href);
+                target.scrollIntoView({ behavior: 'smooth' });
+                if (link.classList.contains('heading-anchor')) {
+                    navigator.clipboard?.writeText(link.href).then(() => {
+                        link.dataset.copied = 'true';
+                        setTimeout(() => delete link.dataset.copied, 1500);
                     });
                 }
             };
-            highlight();
-            const observer = new MutationObserver(highlight);
-            observer.observe(document.body, { childList: true, subtree: true });
-            return () => observer.disconnect();
-        ");
+            document.addEventListener('click', onClick);
+            window.__stopSectionLinks = () => document.removeEventListener('click', onClick);
+        ",
+        );
+    });
+    use_drop(|| {
+        document::eval("window.__stopSectionLinks?.();");
     });
 }
@@ -1,13 +1,20 @@
 // Main entry point for the Dioxus blog application
 use dioxus::prelude::*;
-use views::{About, BlogList, BlogPost, Contact, Home, Navbar, NotFound, ProjectList, ProjectPost};
+use views::{
+    About, CollectionEntry, CollectionList, Contact, Home, Navbar, NotFound, SeriesPage, TagList,
+    TagPage,
+};
 
 mod components;
 mod data;
 mod hooks;
+#[cfg(feature = "prerender")]
+mod prerender;
 mod views;
 
-use data::constants::FAVICON;
+use data::collection::Collection;
+use data::constants::{APP_TITLE, COLLECTIONS};
+use data::utils::SITE_ROOT_URL;
 
 #[derive(Debug, Clone, Routable, PartialEq)]
 enum Route {
@@ -15,34 +22,67 @@ enum Route {
     #[route("/")]
     Home {},
 
-    #[route("/blog")]
-    BlogList {},
+    #[route("/series/:id")]
+    SeriesPage { id: String },
 
-    #[route("/blog/:id")]
-    BlogPost { id: String },
+    #[route("/tags")]
+    TagList {},
 
-    #[route("/projects")]
-    ProjectList {},
-
-    #[route("/projects/:id")]
-    ProjectPost { id: String },
+    #[route("/tags/:tag")]
+    TagPage { tag: String },
 
     #[route("/about")]
     About {},
 
     #[route("/contact")]
     Contact {},
+
+    // Static segments match first, so these only see collection routes from `COLLECTIONS`
+    #[route("/:collection")]
+    CollectionList { collection: String },
+
+    #[route("/:collection/:id")]
+    CollectionEntry { collection: String, id: String },
     #[end_layout]
     #[route("/:..segments")]
     NotFound { segments: Vec<String> },
 }
 
+impl Route {
+    /// The list page of a collection.
+    fn list(collection: &Collection) -> Self {
+        Route::CollectionList {
+            collection: collection.route.to_string(),
+        }
+    }
+
+    /// The detail page of an entry, given its collection's name (`posts`).
+    fn entry(collection: &str, id: &str) -> Self {
+        Route::CollectionEntry {
+            collection: Collection::by_name(COLLECTIONS, collection)
+                .map_or(collection, |collection| collection.route)
+                .to_string(),
+            id: id.to_string(),
+        }
+    }
+}
+
+const FAVICON: Asset = asset!("assets/favicon.png");
 const MAIN_CSS: Asset = asset!("assets/tailwind.css");
 
+#[cfg(not(feature = "prerender"))]
 fn main() {
+    // Without hydration the app renders afresh in place of the prerendered markup
+    #[cfg(all(target_arch = "wasm32", not(feature = "hydrate")))]
+    data::preload::clear_prerendered_markup();
     dioxus::launch(App);
 }
 
+#[cfg(feature = "prerender")]
+fn main() {
+    prerender::run();
+}
+
 /// Detect initial theme (Pure Rust abstraction)
 fn get_initial_theme() -> bool {
     #[cfg(target_arch = "wasm32")]
@@ -62,6 +102,21 @@ fn get_initial_theme() -> bool {
     false
 }
 
+/// Theme of the first render. Hydrated pages start in the theme they were
+/// prerendered in, and `App` switches to the saved one once they are live.
+fn first_render_theme() -> bool {
+    !cfg!(feature = "hydrate") && get_initial_theme()
+}
+
+/// Class naming the theme on the document root and the app's wrapper.
+fn theme_class(is_dark: bool) -> &'static str {
+    if is_dark {
+        "dark"
+    } else {
+        "light"
+    }
+}
+
 /// Sync theme to storage and document root (Pure Rust abstraction)
 #[warn(unused_variables)]
 fn sync_theme(_is_dark: bool) {
@@ -72,6 +127,7 @@ fn sync_theme(_is_dark: bool) {
             if let Some(document) = window.document() {
                 if let Some(root) = document.document_element() {
                     let _ = root.class_list().toggle_with_force("dark", _is_dark);
+                    let _ = root.class_list().toggle_with_force("light", !_is_dark);
                 }
             }
             // 2. Persist to localStorage
@@ -85,9 +141,13 @@ fn sync_theme(_is_dark: bool) {
 #[allow(non_snake_case)]
 #[component]
 fn App() -> Element {
-    let is_dark = use_signal(get_initial_theme);
+    #[allow(unused_mut)]
+    let mut is_dark = use_signal(first_render_theme);
     use_context_provider(|| is_dark);
 
+    // Effects run in order, so `sync_theme` never stores the prerendered theme
+    #[cfg(feature = "hydrate")]
+    use_effect(move || is_dark.set(get_initial_theme()));
     // Platform-agnostic effect
     use_effect(move || sync_theme(is_dark()));
 
@@ -104,12 +164,29 @@ fn App() -> Element {
             href: "https://fonts.googleapis.com/css2?family=Inter:wght@400;700;900&family=Roboto+Mono&family=Material+Symbols+Outlined:wght,FILL@100..700,0..1&display=swap",
             rel: "stylesheet",
         }
-        document::Script { src: "https://cdnjs.cloudflare.com/ajax/libs/highlight.js/11.9.0/highlight.min.js" }
         document::Link { rel: "icon", href: FAVICON }
+        document::Link {
+            rel: "alternate",
+            r#type: "application/rss+xml",
+            title: "{APP_TITLE}",
+            href: "{SITE_ROOT_URL}/feed.xml",
+        }
+        document::Link {
+            rel: "alternate",
+            r#type: "application/atom+xml",
+            title: "{APP_TITLE}",
+            href: "{SITE_ROOT_URL}/atom.xml",
+        }
+        document::Link {
+            rel: "alternate",
+            r#type: "application/feed+json",
+            title: "{APP_TITLE}",
+            href: "{SITE_ROOT_URL}/feed.json",
+        }
         document::Link { rel: "stylesheet", href: MAIN_CSS }
 
         // Root Wrapper: Reacts to is_dark signal
-        div { class: if is_dark() { "dark" } else { "" },
+        div { class: theme_class(is_dark()),
             div { class: "bg-background-light dark:bg-background-dark text-text-dark dark:text-text-light min-h-screen transition-colors duration-300",
                 Router::<Route> {}
             }
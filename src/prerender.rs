@@ -0,0 +1,323 @@
+//! Static site generation for GitHub Pages.
+//!
+//! Renders every `Route` with dioxus SSR, one page per collection and indexed entry,
+//! and writes `{out}/{route}/index.html` into the bundled site, plus `404.html`.
+//! Each page is the bundle's own `index.html` with the rendered body, the head
+//! elements and the files the page loaded embedded, so crawlers get real content.
+//!
+//! A bundle built with the `hydrate` feature hydrates the pages: the markup
+//! carries dioxus' hydration ids, and each page embeds the data dioxus-web reads
+//! before its first render. That render has to produce the same markup, so a
+//! page is the first render of its route. Content resources resolve during it
+//! (`use_eager_resource`), from disk here and from the embedded files in the
+//! browser, while effects and later updates never run.
+//!
+//! ```sh
+//! dx bundle --release --platform web --features hydrate --out-dir dist
+//! cargo run --no-default-features --features prerender -- dist/public
+//! ```
+
+use crate::data::collection::EntryMeta;
+use crate::data::constants::COLLECTIONS;
+use crate::data::html::escape;
+use crate::data::preload::{self, PRELOAD_SCRIPT_ID};
+use crate::data::series::Series;
+use crate::data::taxonomy::TagInfo;
+use crate::{get_initial_theme, theme_class, App, Route};
+use dioxus::core::CapturedError;
+use dioxus::document::{Document, Eval, NoOpDocument};
+use dioxus::history::{History, MemoryHistory};
+use dioxus::prelude::*;
+use dioxus_fullstack_core::HydrationContext;
+use serde::de::DeserializeOwned;
+use std::cell::{Cell, RefCell};
+use std::fs;
+use std::panic::Location;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+const DEFAULT_OUT_DIR: &str = "docs";
+const MOUNT_POINT: &str = r#"<div id="main"></div>"#;
+
+/// Switches the static theme class to the saved or preferred theme before
+/// first paint, mirroring `get_initial_theme`.
+const THEME_SCRIPT: &str = r#"<script>try{var t=localStorage.getItem("theme"),d=t==="dark"||(!t&&matchMedia("(prefers-color-scheme: dark)").matches),c=document.documentElement.classList;c.toggle("dark",d);c.toggle("light",!d)}catch(e){}</script>"#;
+
+thread_local! {
+    static BASE_PATH: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Base path of the site being prerendered, taken from the bundle's `<base>` tag.
+pub fn base_path() -> Option<String> {
+    BASE_PATH.with(|base| base.borrow().clone())
+}
+
+/// Collects what components push into `<head>` while a page renders.
+#[derive(Default)]
+struct HeadCapture {
+    title: RefCell<Option<String>>,
+    elements: RefCell<Vec<String>>,
+    /// Head components created, including the ones deduplicated away.
+    components: Cell<usize>,
+}
+
+impl Document for HeadCapture {
+    fn eval(&self, js: String) -> Eval {
+        NoOpDocument.eval(js)
+    }
+
+    fn set_title(&self, title: String) {
+        *self.title.borrow_mut() = Some(title);
+    }
+
+    fn create_head_element(
+        &self,
+        name: &str,
+        attributes: &[(&str, String)],
+        contents: Option<String>,
+    ) {
+        let attributes: String = attributes
+            .iter()
+            .map(|(key, value)| format!(" {}=\"{}\"", key, escape(value)))
+            .collect();
+        let element = match name {
+            "link" | "meta" => format!("<{name}{attributes}>"),
+            _ => format!(
+                "<{name}{attributes}>{}</{name}>",
+                contents.unwrap_or_default()
+            ),
+        };
+        self.elements.borrow_mut().push(element);
+    }
+
+    fn create_head_component(&self) -> bool {
+        self.components.set(self.components.get() + 1);
+        true
+    }
+}
+
+/// A fully rendered route, ready to be merged into the shell.
+struct Page {
+    title: Option<String>,
+    head: Vec<String>,
+    body: String,
+    preloaded: String,
+    hydration: String,
+}
+
+pub fn run() {
+    // Outside `cargo run`, `asset!` paths are placeholders that only dx can fill in
+    if std::env::var_os("CARGO_MANIFEST_DIR").is_none() {
+        panic!("run the prerenderer through `cargo run` so assets resolve to their source files");
+    }
+    let out_dir = PathBuf::from(
+        std::env::args()
+            .nth(1)
+            .unwrap_or_else(|| DEFAULT_OUT_DIR.to_string()),
+    );
+    let shell_path = out_dir.join("index.html");
+    let shell = fs::read_to_string(&shell_path)
+        .unwrap_or_else(|e| panic!("cannot read {}: {}", shell_path.display(), e));
+    if shell.contains(PRELOAD_SCRIPT_ID) {
+        panic!(
+            "{} is already prerendered; bundle the site again first",
+            shell_path.display()
+        );
+    }
+    if !shell.contains(MOUNT_POINT) {
+        panic!(
+            "{} has no `{}` to render into",
+            shell_path.display(),
+            MOUNT_POINT
+        );
+    }
+    let base_path = read_base_path(&shell);
+    BASE_PATH.with(|base| *base.borrow_mut() = Some(base_path.clone()));
+    let assets = bundled_assets(&out_dir, &base_path);
+
+    let mut routes = Route::static_routes();
+    for collection in COLLECTIONS {
+        routes.push(Route::list(collection));
+        routes.extend(
+            read_index::<EntryMeta>(&format!("content/{}_index.json", collection.name))
+                .into_iter()
+                .map(|entry| Route::entry(collection.name, &entry.id)),
+        );
+    }
+    routes.extend(
+        read_index::<Series>("content/series_index.json")
+            .into_iter()
+            .map(|series| Route::SeriesPage { id: series.id }),
+    );
+    routes.extend(
+        read_index::<TagInfo>("content/tags_index.json")
+            .into_iter()
+            .map(|tag| Route::TagPage { tag: tag.slug }),
+    );
+
+    let pages = routes.iter().map(|route| {
+        let target = out_dir
+            .join(route.to_string().trim_start_matches('/'))
+            .join("index.html");
+        (route.clone(), target)
+    });
+    // GitHub Pages serves this for unknown paths. The app hydrates it as `/404`,
+    // the route it was rendered at, and then routes to the path in the address bar.
+    let not_found = Route::NotFound {
+        segments: vec!["404".to_string()],
+    };
+    for (route, target) in pages.chain([(not_found, out_dir.join("404.html"))]) {
+        let page = render_route(&route, &base_path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        let mut html = assemble(&shell, &page);
+        for (source, bundled) in &assets {
+            html = html.replace(source.as_str(), bundled);
+        }
+        fs::write(&target, html).unwrap();
+        println!("prerendered {} -> {}", route, target.display());
+    }
+}
+
+/// Renders one route as the app's first render of it.
+fn render_route(route: &Route, base_path: &str) -> Page {
+    let head = Rc::new(HeadCapture::default());
+    let history: Rc<dyn History> =
+        Rc::new(MemoryHistory::with_initial_path(route).with_prefix(base_path));
+    let document: Rc<dyn Document> = head.clone();
+    let mut dom = VirtualDom::new(App)
+        .with_root_context(history)
+        .with_root_context(document);
+
+    preload::start_recording();
+    dom.rebuild_in_place();
+    let preloaded = preload::finish_recording();
+
+    let title = head.title.borrow().clone();
+    let hydration = hydration_script(route, head.components.get());
+    let head = head.elements.borrow().clone();
+    Page {
+        title,
+        head,
+        body: dioxus::ssr::pre_render(&dom),
+        // `</` cannot appear inside a script tag; `<\/` is an equivalent JSON escape
+        preloaded: serde_json::to_string(&preloaded)
+            .unwrap()
+            .replace("</", "<\\/"),
+        hydration,
+    }
+}
+
+/// The data dioxus-web reads back while hydrating, in the order it asks for it:
+/// the route the page was rendered at, the error of the root error boundary
+/// (none), and whether each head component's element is already in the page.
+fn hydration_script(route: &Route, head_components: usize) -> String {
+    let context = HydrationContext::default();
+    context
+        .create_entry()
+        .insert(&route.to_string(), Location::caller());
+    context.create_entry::<Option<CapturedError>>();
+    for _ in 0..head_components {
+        context.create_entry().insert(&true, Location::caller());
+    }
+    let data = context.serialized();
+    #[allow(unused_mut)]
+    let mut script = format!(
+        r#"<script>window.initial_dioxus_hydration_data="{}";"#,
+        data.data
+    );
+    // Debug clients name the type and origin of data they fail to read
+    #[cfg(debug_assertions)]
+    script.push_str(&format!(
+        "window.initial_dioxus_hydration_debug_types={};window.initial_dioxus_hydration_debug_locations={};",
+        data.debug_types, data.debug_locations
+    ));
+    script + "</script>"
+}
+
+/// Merges a rendered page into the bundle's `index.html`.
+fn assemble(shell: &str, page: &Page) -> String {
+    let mut html = shell.to_string();
+    if let Some(title) = &page.title {
+        if let (Some(start), Some(end)) = (html.find("<title>"), html.find("</title>")) {
+            html.replace_range(start + "<title>".len()..end, &escape(title));
+        }
+    }
+    set_root_class(&mut html, theme_class(get_initial_theme()));
+    let head = format!("{}{}</head>", page.head.concat(), THEME_SCRIPT);
+    html = html.replacen("</head>", &head, 1);
+    // The data goes first so the app finds it whenever it finds the mount point
+    let main = format!(
+        r#"<script type="application/json" id="{}">{}</script>{}<div id="main">{}</div>"#,
+        PRELOAD_SCRIPT_ID, page.preloaded, page.hydration, page.body
+    );
+    html.replacen(MOUNT_POINT, &main, 1)
+}
+
+/// Adds `class` to the shell's `<html>` tag, so the theme the page was
+/// rendered in holds without scripts.
+fn set_root_class(html: &mut String, class: &str) {
+    let Some(start) = html.find("<html") else {
+        return;
+    };
+    let tag = &html[start..start + html[start..].find('>').unwrap_or(0)];
+    match tag.find("class=\"") {
+        Some(attr) => html.insert_str(start + attr + "class=\"".len(), &format!("{} ", class)),
+        None => html.insert_str(start + "<html".len(), &format!(" class=\"{}\"", class)),
+    }
+}
+
+/// Maps each file in `assets/` to the URL of its copy in the bundle. Under
+/// `cargo run`, `asset!` resolves to the source file, while dx writes it to
+/// `assets/{stem}-{hash}.{ext}`.
+fn bundled_assets(out_dir: &Path, base_path: &str) -> Vec<(String, String)> {
+    let source_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets");
+    let bundled: Vec<String> = fs::read_dir(out_dir.join("assets"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect();
+
+    let mut assets = Vec::new();
+    for entry in fs::read_dir(&source_dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        let (Some(stem), Some(ext)) = (path.file_stem(), path.extension()) else {
+            continue;
+        };
+        let (stem, ext) = (stem.to_string_lossy(), ext.to_string_lossy());
+        let found = bundled.iter().find(|name| {
+            name.ends_with(&format!(".{}", ext))
+                && (name.starts_with(&format!("{}-", stem))
+                    || **name == format!("{}.{}", stem, ext))
+        });
+        match found {
+            Some(name) => assets.push((
+                path.display().to_string(),
+                format!("{}/assets/{}", base_path, name),
+            )),
+            None => eprintln!("warning: {} is not in the bundle", path.display()),
+        }
+    }
+    assets
+}
+
+/// The `href` of the shell's `<base>` tag without its trailing slash, like `get_base_path`.
+fn read_base_path(shell: &str) -> String {
+    shell
+        .split("<base ")
+        .nth(1)
+        .and_then(|tag| tag.split("href=\"").nth(1))
+        .and_then(|href| href.split('"').next())
+        .map(|href| href.trim_end_matches('/').to_string())
+        .unwrap_or_default()
+}
+
+fn read_index<T: DeserializeOwned>(path: &str) -> Vec<T> {
+    let path = Path::new("public").join(path);
+    fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_else(|| panic!("cannot read {}", path.display()))
+}
@@ -47,7 +47,7 @@ pub fn About() -> Element {
                                 "Linux Systems",
                             ]
                             {
-                                Badge { text: skill.to_string() }
+                                Badge { text: skill.to_string(), link: false }
                             }
                         }
                     }
@@ -0,0 +1,255 @@
+use crate::components::{
+    Backlinks, CallToAction, Comments, ContentGallery, DetailHero, EntryBody, GalleryItem,
+    ReadingProgress, RelatedContent, SeriesNav, ShareButtons, TableOfContents, TOC_MIN_ENTRIES,
+};
+use crate::data::collection::{Collection, EntryMeta, FieldKind};
+use crate::data::constants::{APP_TITLE, COLLECTIONS, TIMEZONE_OFFSET_MINUTES};
+use crate::data::content::{
+    derive_categories, fetch_entries, get_entry_by_id, get_series_for_entry,
+};
+use crate::data::date::Date;
+use crate::data::utils::{format_date, get_base_path, get_read_time};
+use crate::hooks::{use_eager_resource, use_scroll_spy, use_section_links};
+use crate::views::NotFound;
+use crate::Route;
+use dioxus::prelude::*;
+
+#[component]
+pub fn CollectionList(collection: String) -> Element {
+    match Collection::by_route(COLLECTIONS, &collection) {
+        Some(collection) => rsx! {
+            EntryGallery { key: "{collection.name}", collection }
+        },
+        None => rsx! {
+            NotFound { segments: vec![collection] }
+        },
+    }
+}
+
+#[component]
+pub fn CollectionEntry(collection: String, id: String) -> Element {
+    match Collection::by_route(COLLECTIONS, &collection) {
+        Some(collection) => rsx! {
+            EntryPage { key: "{collection.name}/{id}", collection, id }
+        },
+        None => rsx! {
+            NotFound { segments: vec![collection, id] }
+        },
+    }
+}
+
+#[component]
+fn EntryGallery(collection: &'static Collection) -> Element {
+    let entries_resource = use_eager_resource(move || fetch_entries(collection));
+
+    let entries_guard = entries_resource.read();
+
+    match &*entries_guard {
+        Some(entries) => {
+            let items = entries
+                .iter()
+                .map(|entry| GalleryItem {
+                    id: entry.id.clone(),
+                    title: entry.title.clone(),
+                    description: entry.description.clone(),
+                    image_url: entry.image_url.clone(),
+                    image: entry.cover.clone(),
+                    tags: entry.tags.clone(),
+                    status: entry.status,
+                })
+                .collect();
+
+            rsx! {
+                document::Title { "{collection.label} - {APP_TITLE}" }
+                ContentGallery {
+                    collection,
+                    items,
+                    categories: derive_categories(entries),
+                }
+            }
+        }
+        None => rsx! {
+            div { class: "flex flex-col items-center justify-center min-h-[60vh]",
+                div { class: "animate-spin rounded-full h-12 w-12 border-t-2 border-b-2 border-primary-light" }
+                p { class: "mt-4 text-text-dark/60 dark:text-text-light/60", "Loading index..." }
+            }
+        },
+    }
+}
+
+#[component]
+fn EntryPage(collection: &'static Collection, id: String) -> Element {
+    let entry_id = id.clone();
+    let entry_resource = use_eager_resource(move || {
+        let id = id.clone();
+        async move { get_entry_by_id(collection, &id).await }
+    });
+    let series_resource = use_eager_resource(move || {
+        let id = entry_id.clone();
+        async move { get_series_for_entry(collection.name, &id).await }
+    });
+
+    use_section_links(entry_resource);
+    let scroll = use_scroll_spy(entry_resource);
+
+    let resource = entry_resource.read();
+    let series = series_resource.read().clone().flatten();
+    match &*resource {
+        Some(Some(entry)) => {
+            let show_toc = entry.toc.len() >= TOC_MIN_ENTRIES;
+            let page = format!(
+                "{}{}",
+                get_base_path(),
+                Route::entry(collection.name, &entry.meta.id)
+            );
+            let active = scroll.read().active.clone();
+            rsx! {
+                document::Title { "{entry.meta.title} - {APP_TITLE}" }
+                ReadingProgress { progress: scroll.read().progress }
+                div {
+                    class: "layout-content-container flex gap-12 items-start w-full mx-auto px-4 sm:px-6 lg:px-8 py-12 sm:py-16",
+                    class: if show_toc { "max-w-6xl" } else { "max-w-4xl" },
+                    article { class: "w-full max-w-3xl min-w-0 flex flex-col gap-10",
+                        DetailHero {
+                            title: entry.meta.title.clone(),
+                            author: entry.meta.author.clone(),
+                            date: entry.meta.date,
+                            read_time: get_read_time(entry.word_count),
+                            back_link: Route::list(collection),
+                            back_label: collection.label.to_string(),
+                            draft: entry.meta.status.is_draft(),
+                            image_url: format!("{}/{}", get_base_path(), entry.meta.image_url),
+                            image: entry.meta.cover.clone(),
+                        }
+
+                        if let Some(series) = series {
+                            SeriesNav {
+                                series,
+                                collection: collection.name,
+                                current: entry.meta.id.clone(),
+                            }
+                        }
+
+                        if show_toc {
+                            div { class: "lg:hidden",
+                                TableOfContents {
+                                    entries: entry.toc.clone(),
+                                    page: page.clone(),
+                                    active: active.clone(),
+                                    collapsible: true,
+                                }
+                            }
+                        }
+
+                        EntryBody { body: entry.body.clone() }
+
+                        EntryFields { collection, meta: entry.meta.clone() }
+
+                        if collection.share {
+                            ShareButtons {}
+                        }
+
+                        Backlinks { entries: entry.backlinks.clone() }
+
+                        RelatedContent { entries: entry.related.clone() }
+
+                        Comments { collection, id: entry.meta.id.clone() }
+
+                        if collection.call_to_action {
+                            CallToAction {}
+                        }
+                    }
+
+                    if show_toc {
+                        aside { class: "hidden lg:block w-64 shrink-0 sticky top-24 max-h-[calc(100vh-8rem)] overflow-y-auto",
+                            TableOfContents { entries: entry.toc.clone(), page, active }
+                        }
+                    }
+                }
+            }
+        }
+        Some(None) => rsx! {
+            div { class: "flex flex-col items-center justify-center min-h-[60vh]",
+                h1 { class: "text-4xl font-bold", "{collection.item} Not Found" }
+                Link {
+                    to: Route::list(collection),
+                    class: "mt-4 text-primary-light hover:underline",
+                    "Back to {collection.label}"
+                }
+            }
+        },
+        None => rsx! {
+            div { class: "flex flex-col items-center justify-center min-h-[60vh]",
+                div { class: "animate-spin rounded-full h-12 w-12 border-t-2 border-b-2 border-primary-light" }
+                p { class: "mt-4 text-text-dark/60 dark:text-text-light/60",
+                    "Loading {collection.item.to_lowercase()}..."
+                }
+            }
+        },
+    }
+}
+
+/// The collection's labelled fields: links as buttons, everything else as a list.
+#[component]
+fn EntryFields(collection: &'static Collection, meta: EntryMeta) -> Element {
+    let mut links = Vec::new();
+    let mut details = Vec::new();
+    for field in collection.fields {
+        let (Some(label), Some(value)) = (field.label, meta.fields.get(field.name)) else {
+            continue;
+        };
+        match &field.kind {
+            FieldKind::Link { text_field } => {
+                let text = text_field.and_then(|name| meta.text(name)).unwrap_or(label);
+                if let Some(href) = value.as_str() {
+                    links.push((href.to_string(), text.to_string()));
+                }
+            }
+            kind => {
+                let value = match kind {
+                    FieldKind::Bool if value.as_bool() == Some(true) => "Yes".to_string(),
+                    FieldKind::Bool => "No".to_string(),
+                    FieldKind::Date => value
+                        .as_str()
+                        .and_then(|text| Date::parse(text, TIMEZONE_OFFSET_MINUTES))
+                        .map(|date| format_date(&date))
+                        .unwrap_or_default(),
+                    FieldKind::List => value
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|item| item.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    _ => value
+                        .as_str()
+                        .map_or_else(|| value.to_string(), str::to_string),
+                };
+                details.push((label, value));
+            }
+        }
+    }
+
+    rsx! {
+        if !details.is_empty() {
+            dl { class: "grid grid-cols-[max-content_1fr] gap-x-6 gap-y-2 text-sm",
+                for (label , value) in details {
+                    dt { class: "font-semibold text-text-dark/60 dark:text-gray-400", "{label}" }
+                    dd { class: "text-text-dark dark:text-white", "{value}" }
+                }
+            }
+        }
+        for (href , text) in links {
+            div { class: "mt-8",
+                a {
+                    href: "{href}",
+                    class: "inline-flex items-center gap-2 bg-primary-light text-text-dark px-6 py-3 rounded-lg font-bold hover:opacity-90 transition-all shadow-md active:scale-95",
+                    target: "_blank",
+                    rel: "noopener noreferrer",
+                    span { class: "material-symbols-outlined", "launch" }
+                    "{text}"
+                }
+            }
+        }
+    }
+}
@@ -2,10 +2,50 @@ use crate::components::{Container, Hero, Input, PrimaryButton, Section, SectionT
 use crate::data::constants::{
     APP_TITLE, AUTHOR_EMAIL, AUTHOR_NAME, GITHUB_URL, GITHUB_USERNAME, LINKEDIN_URL,
 };
+use crate::data::contact_form::{backend, ContactBackend, ContactMessage, Delivery};
 use dioxus::prelude::*;
 
+#[derive(Clone, PartialEq)]
+enum SendStatus {
+    Editing,
+    Sending,
+    Done(Delivery),
+    Failed(String),
+}
+
 #[component]
 pub fn Contact() -> Element {
+    let mut name = use_signal(String::new);
+    let mut email = use_signal(String::new);
+    let mut message = use_signal(String::new);
+    let mut status = use_signal(|| SendStatus::Editing);
+    let sending = status() == SendStatus::Sending;
+
+    let onsubmit = move |e: FormEvent| {
+        e.prevent_default();
+        if sending {
+            return;
+        }
+        let contact = ContactMessage {
+            name: name(),
+            email: email(),
+            message: message(),
+        };
+        status.set(SendStatus::Sending);
+        spawn(async move {
+            status.set(match backend().send(&contact).await {
+                Ok(delivery) => SendStatus::Done(delivery),
+                Err(error) => SendStatus::Failed(error),
+            });
+        });
+    };
+    let reset = move |_| {
+        name.set(String::new());
+        email.set(String::new());
+        message.set(String::new());
+        status.set(SendStatus::Editing);
+    };
+
     rsx! {
         document::Title { "Contact - {APP_TITLE}" }
         Container {
@@ -17,33 +57,46 @@ pub fn Contact() -> Element {
             Section { class: "px-4 mb-20",
                 div { class: "grid grid-cols-1 md:grid-cols-3 gap-8 md:gap-12",
                     div { class: "md:col-span-2 bg-white dark:bg-[#2a2a2a] p-8 rounded-lg border border-text-dark/5 dark:border-white/10 transition-colors shadow-sm",
-                        form {
-                            class: "flex flex-col gap-6",
-                            onsubmit: |e| e.prevent_default(),
-                            SectionTitle { title: "Send a Message" }
-                            div { class: "flex flex-col sm:flex-row gap-6",
-                                Input {
-                                    label: Some("Your Name".to_string()),
-                                    id: "name",
-                                    placeholder: "John Doe",
+                        if let SendStatus::Done(delivery) = status() {
+                            Confirmation { delivery, name: name(), onreset: reset }
+                        } else {
+                            form { class: "flex flex-col gap-6", onsubmit,
+                                SectionTitle { title: "Send a Message" }
+                                div { class: "flex flex-col sm:flex-row gap-6",
+                                    Input {
+                                        label: Some("Your Name".to_string()),
+                                        id: "name",
+                                        placeholder: "John Doe",
+                                        oninput: move |e: FormEvent| name.set(e.value()),
+                                    }
+                                    Input {
+                                        label: Some("Your Email".to_string()),
+                                        id: "email",
+                                        placeholder: "john.doe@email.com",
+                                        r#type: "email",
+                                        oninput: move |e: FormEvent| email.set(e.value()),
+                                    }
                                 }
-                                Input {
-                                    label: Some("Your Email".to_string()),
-                                    id: "email",
-                                    placeholder: "john.doe@email.com",
-                                    r#type: "email",
+                                TextArea {
+                                    label: Some("Message".to_string()),
+                                    id: "message",
+                                    placeholder: "I'd like to discuss...",
+                                    rows: 6,
+                                    oninput: move |e: FormEvent| message.set(e.value()),
                                 }
-                            }
-                            TextArea {
-                                label: Some("Message".to_string()),
-                                id: "message",
-                                placeholder: "I'd like to discuss...",
-                                rows: 6,
-                            }
-                            div { class: "flex justify-start",
-                                PrimaryButton {
-                                    text: "Submit Message",
-                                    onclick: move |_| {},
+                                if let SendStatus::Failed(error) = status() {
+                                    p {
+                                        class: "flex items-center gap-2 text-sm text-red-600 dark:text-red-400",
+                                        role: "alert",
+                                        span { class: "material-symbols-outlined text-base", "error" }
+                                        "{error}"
+                                    }
+                                }
+                                div { class: "flex justify-start",
+                                    PrimaryButton {
+                                        text: if sending { "Sending..." } else { "Submit Message" },
+                                        disabled: sending,
+                                    }
                                 }
                             }
                         }
@@ -77,6 +130,47 @@ pub fn Contact() -> Element {
     }
 }
 
+/// Shown in place of the form once a message is on its way.
+#[component]
+fn Confirmation(delivery: Delivery, name: String, onreset: EventHandler<MouseEvent>) -> Element {
+    let (icon, title) = match delivery {
+        Delivery::Sent => ("mark_email_read", "Message sent"),
+        Delivery::MailApp => ("forward_to_inbox", "Almost there"),
+        Delivery::Logged => ("terminal", "Dry run complete"),
+    };
+    let name = name.trim().to_string();
+
+    rsx! {
+        div { class: "flex flex-col items-start gap-4", role: "status",
+            div { class: "flex items-center justify-center size-12 bg-primary-light/15 rounded-full text-primary-light",
+                span { class: "material-symbols-outlined", "{icon}" }
+            }
+            SectionTitle { title }
+            p { class: "text-text-dark/70 dark:text-[#D4D4D4] leading-relaxed",
+                match delivery {
+                    Delivery::Sent => rsx! { "Thanks, {name}! Your message arrived and I'll get back to you soon." },
+                    Delivery::MailApp => rsx! {
+                        "Your mail app should have opened with the message filled in; press send there to deliver it. If nothing opened, write to "
+                        a {
+                            class: "text-primary-light hover:underline",
+                            href: "mailto:{AUTHOR_EMAIL}",
+                            "{AUTHOR_EMAIL}"
+                        }
+                        "."
+                    },
+                    Delivery::Logged => rsx! { "The message was written to the browser console instead of being sent." },
+                }
+            }
+            button {
+                class: "text-primary-light text-sm font-semibold hover:underline flex items-center gap-1",
+                onclick: move |e| onreset.call(e),
+                span { class: "material-symbols-outlined text-base", "edit" }
+                "Send another message"
+            }
+        }
+    }
+}
+
 #[component]
 fn ContactInfoItem(icon: String, label: String, value: String, href: String) -> Element {
     rsx! {
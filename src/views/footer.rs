@@ -1,4 +1,4 @@
-use crate::data::constants::{APP_TITLE, COPYRIGHT_YEAR};
+use crate::data::constants::{APP_TITLE, COLLECTIONS, COPYRIGHT_YEAR};
 use crate::Route;
 use dioxus::prelude::*;
 
@@ -16,15 +16,17 @@ pub fn Footer() -> Element {
                         to: Route::Home {},
                         "Home"
                     }
-                    Link {
-                        class: "hover:text-primary-light transition-all hover:-translate-y-0.5",
-                        to: Route::BlogList {},
-                        "Blog"
+                    for collection in COLLECTIONS {
+                        Link {
+                            class: "hover:text-primary-light transition-all hover:-translate-y-0.5",
+                            to: Route::list(collection),
+                            "{collection.label}"
+                        }
                     }
                     Link {
                         class: "hover:text-primary-light transition-all hover:-translate-y-0.5",
-                        to: Route::ProjectList {},
-                        "Projects"
+                        to: Route::TagList {},
+                        "Tags"
                     }
                     Link {
                         class: "hover:text-primary-light transition-all hover:-translate-y-0.5",
@@ -1,60 +1,47 @@
 use crate::components::*;
-use crate::data::blog::fetch_all_posts;
-use crate::data::constants::{APP_SUBTITLE, APP_TITLE};
-use crate::data::projects::fetch_all_projects;
+use crate::data::constants::{APP_SUBTITLE, APP_TITLE, COLLECTIONS};
+use crate::data::content::fetch_all_collections;
 use crate::data::utils::get_base_path;
-use crate::views::Footer;
+use crate::hooks::use_eager_resource;
 use crate::Route;
 use dioxus::prelude::*;
 
 #[component]
 pub fn Home() -> Element {
-    let posts_resource = use_resource(fetch_all_posts);
-    let projects_resource = use_resource(fetch_all_projects);
+    let collections_resource = use_eager_resource(fetch_all_collections);
 
-    let posts = posts_resource.read();
-    let projects = projects_resource.read();
+    let collections = collections_resource.read();
 
-    if let (Some(posts), Some(projects)) = (&*posts, &*projects) {
-        // Create a unified list of recent items using iterators
-        let mut recent_items: Vec<_> = posts
+    if let Some(collections) = &*collections {
+        // Create a unified list of recent items across every collection.
+        // Unlisted entries stay reachable by URL but never surface here.
+        let mut recent_items: Vec<_> = collections
             .iter()
-            .map(|post| {
-                (
-                    post.date.clone(),
-                    rsx! {
-                        Card {
-                            title: post.title.clone(),
-                            description: post.description.clone(),
-                            image_url: format!("{}/{}", get_base_path(), post.image_url),
-                            tags: post.tags.clone(),
-                            link_to: Route::BlogPost {
-                                id: post.id.clone(),
+            .flat_map(|(collection, entries)| {
+                entries
+                    .iter()
+                    .filter(|entry| entry.status.is_listed())
+                    .map(|entry| {
+                        (
+                            entry.date,
+                            rsx! {
+                                Card {
+                                    title: entry.title.clone(),
+                                    description: entry.description.clone(),
+                                    image_url: format!("{}/{}", get_base_path(), entry.image_url),
+                                    image: entry.cover.clone(),
+                                    tags: entry.tags.clone(),
+                                    link_to: Route::entry(collection.name, &entry.id),
+                                    draft: entry.status.is_draft(),
+                                }
                             },
-                        }
-                    },
-                )
+                        )
+                    })
             })
-            .chain(projects.iter().map(|project| {
-                (
-                    project.date.clone(),
-                    rsx! {
-                        Card {
-                            title: project.title.clone(),
-                            description: project.description.clone(),
-                            image_url: format!("{}/{}", get_base_path(), project.image_url),
-                            tags: project.tags.clone(),
-                            link_to: Route::ProjectPost {
-                                id: project.id.clone(),
-                            },
-                        }
-                    },
-                )
-            }))
             .collect();
 
         // Sort by date descending
-        recent_items.sort_by(|a, b| b.0.cmp(&a.0));
+        recent_items.sort_by_key(|(date, _)| std::cmp::Reverse(*date));
 
         // Take the 3 most recent
         let latest_elements = recent_items.into_iter().take(3).map(|(_, el)| el);
@@ -64,7 +51,10 @@ pub fn Home() -> Element {
             Container {
                 main { class: "flex flex-col gap-16 md:gap-24 mt-8 md:mt-16",
                     Hero { title: "{APP_TITLE}", subtitle: "{APP_SUBTITLE}",
-                        PrimaryButton { to: Route::BlogList {}, text: "Explore the Blog" }
+                        PrimaryButton {
+                            to: Route::list(&COLLECTIONS[0]),
+                            text: "Explore the {COLLECTIONS[0].label}",
+                        }
                     }
 
                     Section {
@@ -1,17 +1,19 @@
 pub mod about;
-pub mod blog;
+pub mod collection;
 pub mod contact;
 pub mod footer;
 pub mod home;
 pub mod navbar;
 pub mod not_found;
-pub mod projects;
+pub mod series;
+pub mod tags;
 
 pub use about::About;
-pub use blog::{BlogList, BlogPost};
+pub use collection::{CollectionEntry, CollectionList};
 pub use contact::Contact;
 pub use footer::Footer;
 pub use home::Home;
 pub use navbar::Navbar;
 pub use not_found::NotFound;
-pub use projects::{ProjectList, ProjectPost};
+pub use series::SeriesPage;
+pub use tags::{TagList, TagPage};
@@ -1,6 +1,7 @@
-use crate::data::constants::{APP_TITLE, FAVICON};
+use crate::data::constants::{APP_TITLE, COLLECTIONS};
 use crate::views::Footer;
 use crate::Route;
+use crate::FAVICON;
 use dioxus::prelude::*;
 
 #[component]
@@ -21,8 +22,9 @@ pub fn Navbar() -> Element {
             div { class: "flex flex-1 justify-end items-center gap-4",
                 nav { class: "hidden md:flex items-center gap-8",
                     NavLink { to: Route::Home {}, "Home" }
-                    NavLink { to: Route::BlogList {}, "Blog" }
-                    NavLink { to: Route::ProjectList {}, "Projects" }
+                    for collection in COLLECTIONS {
+                        NavLink { to: Route::list(collection), "{collection.label}" }
+                    }
                     NavLink { to: Route::About {}, "About" }
                     NavLink { to: Route::Contact {}, "Contact" }
                 }
@@ -62,15 +64,12 @@ pub fn Navbar() -> Element {
                         onclick: move |_| mobile_menu_open.set(false),
                         "Home"
                     }
-                    MobileLink {
-                        to: Route::BlogList {},
-                        onclick: move |_| mobile_menu_open.set(false),
-                        "Blog"
-                    }
-                    MobileLink {
-                        to: Route::ProjectList {},
-                        onclick: move |_| mobile_menu_open.set(false),
-                        "Projects"
+                    for collection in COLLECTIONS {
+                        MobileLink {
+                            to: Route::list(collection),
+                            onclick: move |_| mobile_menu_open.set(false),
+                            "{collection.label}"
+                        }
                     }
                     MobileLink {
                         to: Route::About {},
@@ -104,8 +103,11 @@ fn NavLink(to: Route, children: Element) -> Element {
     // Simple check: Exact match or prefix match for some routes could be added if needed.
     // Dioxus router matching logic:
     let is_active = current_route == to
-        || (to == Route::BlogList {} && matches!(current_route, Route::BlogPost { .. }))
-        || (to == Route::ProjectList {} && matches!(current_route, Route::ProjectPost { .. }));
+        || matches!(
+            (&to, &current_route),
+            (Route::CollectionList { collection }, Route::CollectionEntry { collection: current, .. })
+                if collection == current
+        );
 
     let active_class = if is_active {
         "text-primary-light"
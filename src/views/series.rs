@@ -0,0 +1,92 @@
+use crate::data::collection::Collection;
+use crate::data::constants::{APP_TITLE, COLLECTIONS};
+use crate::data::content::get_series_by_id;
+use crate::data::utils::format_date;
+use crate::hooks::use_eager_resource;
+use crate::Route;
+use dioxus::prelude::*;
+
+#[component]
+pub fn SeriesPage(id: String) -> Element {
+    let series_resource = use_eager_resource(move || {
+        let id = id.clone();
+        async move { get_series_by_id(&id).await }
+    });
+
+    let resource = series_resource.read();
+    match &*resource {
+        Some(Some(series)) => {
+            let count = series.parts.len();
+            // The breadcrumb leads back to the collection of the first part
+            let collection = series
+                .parts
+                .first()
+                .and_then(|part| Collection::by_name(COLLECTIONS, &part.collection))
+                .unwrap_or(&COLLECTIONS[0]);
+            rsx! {
+                document::Title { "{series.title} - {APP_TITLE}" }
+                div { class: "layout-content-container flex flex-col w-full max-w-4xl mx-auto px-4 sm:px-6 lg:px-8 py-12 sm:py-16",
+                    header { class: "flex flex-col gap-4 border-b border-text-dark/5 dark:border-white/5 pb-10",
+                        div { class: "flex items-center gap-2 text-sm text-text-dark/60 dark:text-gray-500 font-medium",
+                            Link {
+                                class: "hover:text-primary-light transition-colors",
+                                to: Route::list(collection),
+                                "{collection.label}"
+                            }
+                            span { "›" }
+                            span { "Series" }
+                        }
+                        h1 { class: "text-text-dark dark:text-white text-4xl sm:text-5xl font-extrabold leading-tight tracking-tight",
+                            "{series.title}"
+                        }
+                        p { class: "text-sm text-gray-400",
+                            if count == 1 {
+                                "1 part"
+                            } else {
+                                "{count} parts"
+                            }
+                        }
+                    }
+                    ol { class: "flex flex-col gap-6 mt-10",
+                        for (idx , part) in series.parts.iter().enumerate() {
+                            li { key: "{part.id}", class: "flex gap-5",
+                                span { class: "flex-none flex items-center justify-center size-10 rounded-full bg-primary-light/10 text-primary-light font-bold",
+                                    "{idx + 1}"
+                                }
+                                div { class: "flex flex-col gap-1",
+                                    Link {
+                                        class: "text-xl font-bold text-text-dark dark:text-white hover:text-primary-light transition-colors",
+                                        to: Route::entry(&part.collection, &part.id),
+                                        "{part.title}"
+                                    }
+                                    time {
+                                        class: "text-sm text-gray-400",
+                                        datetime: "{part.date}",
+                                        "{format_date(&part.date)}"
+                                    }
+                                    p { class: "text-text-dark/70 dark:text-gray-400", "{part.description}" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Some(None) => rsx! {
+            div { class: "flex flex-col items-center justify-center min-h-[60vh]",
+                h1 { class: "text-4xl font-bold", "Series Not Found" }
+                Link {
+                    to: Route::list(&COLLECTIONS[0]),
+                    class: "mt-4 text-primary-light hover:underline",
+                    "Back to {COLLECTIONS[0].label}"
+                }
+            }
+        },
+        None => rsx! {
+            div { class: "flex flex-col items-center justify-center min-h-[60vh]",
+                div { class: "animate-spin rounded-full h-12 w-12 border-t-2 border-b-2 border-primary-light" }
+                p { class: "mt-4 text-text-dark/60 dark:text-text-light/60", "Loading series..." }
+            }
+        },
+    }
+}
@@ -0,0 +1,157 @@
+use crate::components::{Card, Container, Hero, Section};
+use crate::data::constants::APP_TITLE;
+use crate::data::content::fetch_all_collections;
+use crate::data::tags::{fetch_all_tags, get_tag_by_slug};
+use crate::data::utils::get_base_path;
+use crate::hooks::use_eager_resource;
+use crate::Route;
+use dioxus::prelude::*;
+
+/// Font sizes of the tag cloud, from the least to the most used tag.
+const CLOUD_SIZES: &[&str] = &["text-sm", "text-base", "text-xl", "text-2xl", "text-4xl"];
+
+/// Tag cloud size for a tag used `count` times, scaled against the most used one.
+fn cloud_size(count: usize, max: usize) -> &'static str {
+    let steps = CLOUD_SIZES.len() - 1;
+    let step = if max > 1 {
+        (count.saturating_sub(1) * steps) / (max - 1)
+    } else {
+        0
+    };
+    CLOUD_SIZES[step.min(steps)]
+}
+
+#[component]
+pub fn TagList() -> Element {
+    let tags_resource = use_eager_resource(fetch_all_tags);
+
+    let tags_guard = tags_resource.read();
+    match &*tags_guard {
+        Some(tags) => {
+            let max = tags.iter().map(|tag| tag.count()).max().unwrap_or(0);
+            rsx! {
+                document::Title { "Tags - {APP_TITLE}" }
+                Container {
+                    main { class: "flex flex-col gap-12 mt-8 md:mt-16 mb-20",
+                        Hero {
+                            title: "Tags",
+                            subtitle: "Every topic covered across the blog and projects.",
+                        }
+                        div { class: "flex flex-wrap items-baseline justify-center gap-x-6 gap-y-4 max-w-4xl mx-auto px-4",
+                            for tag in tags {
+                                Link {
+                                    key: "{tag.slug}",
+                                    class: "font-bold text-text-dark/80 dark:text-gray-300 hover:opacity-75 transition-opacity {cloud_size(tag.count(), max)}",
+                                    style: tag.color.as_ref().map(|color| format!("color: {}", color)),
+                                    title: tag.description.clone(),
+                                    to: Route::TagPage {
+                                        tag: tag.slug.clone(),
+                                    },
+                                    "{tag.name}"
+                                    sup { class: "ml-1 text-xs font-normal text-text-dark/50 dark:text-gray-500",
+                                        "{tag.count()}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        None => rsx! {
+            div { class: "flex flex-col items-center justify-center min-h-[60vh]",
+                div { class: "animate-spin rounded-full h-12 w-12 border-t-2 border-b-2 border-primary-light" }
+                p { class: "mt-4 text-text-dark/60 dark:text-text-light/60", "Loading tags..." }
+            }
+        },
+    }
+}
+
+#[component]
+pub fn TagPage(tag: String) -> Element {
+    let tag_resource = use_eager_resource(move || {
+        let slug = tag.clone();
+        async move {
+            let tag = get_tag_by_slug(&slug).await?;
+            let collections = fetch_all_collections().await;
+            Some((tag, collections))
+        }
+    });
+
+    let resource = tag_resource.read();
+    match &*resource {
+        Some(Some((tag, collections))) => {
+            let base_path = get_base_path();
+            let count = tag.count();
+            // Only feed collections have per-tag feeds
+            let has_feed = collections.iter().any(|(collection, _)| {
+                collection.feed && !tag.entries_in(collection.name).is_empty()
+            });
+            rsx! {
+                document::Title { "#{tag.name} - {APP_TITLE}" }
+                Container {
+                    main { class: "flex flex-col gap-12 mt-8 md:mt-16",
+                        Hero {
+                            title: "#{tag.name}",
+                            subtitle: tag.description.clone(),
+                            div { class: "flex items-center gap-4 text-sm text-text-dark/60 dark:text-gray-400",
+                                span {
+                                    if count == 1 {
+                                        "1 entry"
+                                    } else {
+                                        "{count} entries"
+                                    }
+                                }
+                                Link {
+                                    class: "hover:text-primary-light transition-colors",
+                                    to: Route::TagList {},
+                                    "All tags"
+                                }
+                                if has_feed {
+                                    a {
+                                        class: "flex items-center gap-1 hover:text-primary-light transition-colors",
+                                        href: "{base_path}/tags/{tag.slug}/feed.xml",
+                                        span { class: "material-symbols-outlined text-base", "rss_feed" }
+                                        "Feed"
+                                    }
+                                }
+                            }
+                        }
+                        Section { class: "grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 gap-8 mb-20 px-4",
+                            for (collection , entries) in collections {
+                                for entry in entries.iter().filter(|entry| tag.entries_in(collection.name).contains(&entry.id)) {
+                                    Card {
+                                        key: "{collection.name}/{entry.id}",
+                                        title: entry.title.clone(),
+                                        description: entry.description.clone(),
+                                        image_url: format!("{}/{}", base_path, entry.image_url),
+                                        image: entry.cover.clone(),
+                                        tags: entry.tags.clone(),
+                                        link_to: Route::entry(collection.name, &entry.id),
+                                        draft: entry.status.is_draft(),
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Some(None) => rsx! {
+            div { class: "flex flex-col items-center justify-center min-h-[60vh]",
+                h1 { class: "text-4xl font-bold", "Tag Not Found" }
+                Link {
+                    to: Route::TagList {},
+                    class: "mt-4 text-primary-light hover:underline",
+                    "Browse all tags"
+                }
+            }
+        },
+        None => rsx! {
+            div { class: "flex flex-col items-center justify-center min-h-[60vh]",
+                div { class: "animate-spin rounded-full h-12 w-12 border-t-2 border-b-2 border-primary-light" }
+                p { class: "mt-4 text-text-dark/60 dark:text-text-light/60", "Loading tag..." }
+            }
+        },
+    }
+}